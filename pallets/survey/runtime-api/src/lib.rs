@@ -0,0 +1,76 @@
+//! Runtime API definition for the survey pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_survey::{RewardBreakdown, RewardReceipt, Status};
+use sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// API to query the state of surveys.
+    pub trait SurveyApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns the account proposed as next owner of a survey, if any.
+        fn pending_owner(survey_id: u128) -> Option<AccountId>;
+
+        /// Returns the account which funded a survey, if any.
+        fn survey_funder(survey_id: u128) -> Option<AccountId>;
+
+        /// Returns the ids of the surveys with the given status.
+        /// Private surveys are only returned if `include_private` is set.
+        fn surveys_by_status(status: Status, include_private: bool) -> Vec<u128>;
+
+        /// Returns the ids of every survey of an owner, including private ones.
+        fn surveys_of_owner(owner: AccountId) -> Vec<u128>;
+
+        /// Returns the ids of every survey a participant is registered to.
+        fn surveys_joined_by(participant: AccountId) -> Vec<u128>;
+
+        /// Returns the surveys a participant is registered to but not rewarded yet, with the
+        /// reward net of fees it is owed in each. Cancelled surveys and surveys whose reward is not
+        /// known yet are left out.
+        fn pending_claims(participant: AccountId) -> Vec<(u128, Balance)>;
+
+        /// Returns up to `limit` rewarded participants of a survey, starting after `start` if set.
+        /// Passing the last returned participant as `start` resumes the listing.
+        fn list_rewarded(survey_id: u128, start: Option<AccountId>, limit: u32) -> Vec<AccountId>;
+
+        /// Returns the number of decimals of the currency rewards are paid in.
+        fn reward_currency_decimals() -> u8;
+
+        /// Returns the whole and fractional parts of an amount of the reward currency.
+        fn format_balance(amount: Balance) -> (Balance, Balance);
+
+        /// Returns the number of participants of a survey not rewarded yet and the total of their
+        /// rewards, without rewarding them.
+        fn reward_all_preview(survey_id: u128) -> (u32, Balance);
+
+        /// Returns the number of blocks left until the deadline of a survey, 0 once it has passed.
+        fn blocks_until_deadline(survey_id: u128) -> Option<BlockNumber>;
+
+        /// Returns the escrow of a survey left once the rewards owed to its registered but not yet
+        /// rewarded participants are set aside.
+        fn escrow_surplus(survey_id: u128) -> Option<Balance>;
+
+        /// Returns the number of registered, rewarded and pending participants of a survey, and
+        /// its remaining escrow.
+        fn reward_breakdown(survey_id: u128) -> Option<RewardBreakdown<Balance>>;
+
+        /// Returns the share of the funding of a survey paid out as rewards so far.
+        fn reward_utilization(survey_id: u128) -> Option<Perbill>;
+
+        /// Returns the number of surveys, archived surveys excluded.
+        fn total_surveys() -> u64;
+
+        /// Returns the receipt of the reward paid to a participant of a survey, if rewarded.
+        fn reward_receipt(
+            survey_id: u128,
+            participant: AccountId,
+        ) -> Option<RewardReceipt<Balance, BlockNumber>>;
+    }
+}
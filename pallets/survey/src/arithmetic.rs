@@ -0,0 +1,30 @@
+//! Checked arithmetic on survey counts, rewards and escrows.
+//!
+//! Every helper fails with a defensive error instead of wrapping, so callers never fall back to
+//! raw operators on balances.
+
+use crate::pallet::{Config, Error};
+use sp_runtime::traits::{CheckedAdd, CheckedMul, CheckedSub, One};
+
+/// Count one more participant.
+pub(crate) fn add_participant_count<T: Config, B: CheckedAdd + One>(
+    count: B,
+) -> Result<B, Error<T>> {
+    count
+        .checked_add(&B::one())
+        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+}
+
+/// Take `amount` out of an escrow.
+pub(crate) fn sub_escrow<T: Config, B: CheckedSub>(escrow: B, amount: B) -> Result<B, Error<T>> {
+    escrow
+        .checked_sub(&amount)
+        .ok_or(Error::<T>::DefensiveNotEnoughFundsInSurveyForReward)
+}
+
+/// Multiply a reward by a number of slots or a multiplier.
+pub(crate) fn mul_reward<T: Config, B: CheckedMul>(reward: B, factor: B) -> Result<B, Error<T>> {
+    reward
+        .checked_mul(&factor)
+        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+}
@@ -2,39 +2,146 @@
 #![allow(unreachable_code)]
 pub use pallet::*;
 
+mod arithmetic;
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
+#[cfg(test)]
+mod mock_u64;
+
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod tests_u64;
+
 // #[cfg(feature = "runtime-benchmarks")]
 // mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
+    use codec::FullCodec;
     use frame_support::{
         log,
         pallet_prelude::*,
-        traits::{fungible},
+        storage::IterableStorageDoubleMap,
+        traits::{
+            fungible, fungibles,
+            tokens::{Fortitude, Precision, Preservation},
+            Randomness,
+        },
     };
 
+    use crate::arithmetic;
     use frame_system::pallet_prelude::*;
+    use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
     use sp_runtime::{
-        traits::{CheckedAdd, CheckedDiv, CheckedSub},
+        traits::{
+            CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, TrailingZeroInput,
+            Zero,
+        },
+        BoundedBTreeSet, Perbill, SaturatedConversion,
     };
 
+    /// Target of every log emitted by this pallet.
+    pub const LOG_TARGET: &str = "pallet-survey";
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     pub type AccountId<T> = <T as frame_system::Config>::AccountId;
     type BalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<AccountId<T>>>::Balance;
+    type AssetIdOf<T> = <<T as Config>::BonusAssets as fungibles::Inspect<AccountId<T>>>::AssetId;
+    type AssetBalanceOf<T> =
+        <<T as Config>::BonusAssets as fungibles::Inspect<AccountId<T>>>::Balance;
 
     // Type abstractions for easier potential later modification
-    type SurveyId = u128;
     type OwnerId<T> = AccountId<T>;
     type FunderId<T> = AccountId<T>;
     type ParticipantId<T> = AccountId<T>;
+    type TemplateId = u32;
+
+    // TRAITS
+    /// Handler locking the rewards of participants under a vesting schedule.
+    pub trait RewardVesting<AccountId, Balance, BlockNumber> {
+        /// Lock `amount`, already credited to `who`, so that it unlocks linearly over `vesting_blocks`.
+        fn vest_reward(who: &AccountId, amount: Balance, vesting_blocks: BlockNumber) -> DispatchResult;
+    }
+
+    /// Rewards are never locked and are immediately spendable.
+    impl<AccountId, Balance, BlockNumber> RewardVesting<AccountId, Balance, BlockNumber> for () {
+        fn vest_reward(_: &AccountId, _: Balance, _: BlockNumber) -> DispatchResult {
+            Ok(())
+        }
+    }
+
+    /// Hooks notifying other pallets of the lifecycle of surveys.
+    pub trait SurveyLifecycleHooks<AccountId, SurveyId> {
+        /// Called when `owner_id` creates a survey.
+        fn on_created(survey_id: SurveyId, owner_id: &AccountId);
+        /// Called when `funder_id` funds a survey.
+        fn on_funded(survey_id: SurveyId, funder_id: &AccountId);
+        /// Called when a survey becomes completed.
+        fn on_completed(survey_id: SurveyId);
+    }
+
+    /// Nothing reacts to the lifecycle of surveys.
+    impl<AccountId, SurveyId> SurveyLifecycleHooks<AccountId, SurveyId> for () {
+        fn on_created(_: SurveyId, _: &AccountId) {}
+        fn on_funded(_: SurveyId, _: &AccountId) {}
+        fn on_completed(_: SurveyId) {}
+    }
+
+    /// Balance an account can fund surveys and pay their deposits and fees with.
+    pub trait FundingSource<AccountId, Balance> {
+        /// Part of the balance of `who` which can be taken to fund a survey or pay for it.
+        fn fundable_balance(who: &AccountId) -> Balance;
+    }
+
+    /// Funds surveys out of the spendable balance, keeping the existential deposit, holds and
+    /// freezes in place.
+    pub struct SpendableBalance<T>(PhantomData<T>);
+    impl<T: Config> FundingSource<AccountId<T>, BalanceOf<T>> for SpendableBalance<T> {
+        fn fundable_balance(who: &AccountId<T>) -> BalanceOf<T> {
+            <T::NativeBalance as fungible::Inspect<AccountId<T>>>::reducible_balance(
+                who,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            )
+        }
+    }
+
+    /// Check of whether an account may participate in a survey.
+    pub trait EligibilityCheck<AccountId, SurveyId> {
+        /// Whether `who` may be registered to a survey.
+        fn is_eligible(who: &AccountId, survey_id: SurveyId) -> bool;
+    }
+
+    /// Every account is eligible.
+    impl<AccountId, SurveyId> EligibilityCheck<AccountId, SurveyId> for () {
+        fn is_eligible(_: &AccountId, _: SurveyId) -> bool {
+            true
+        }
+    }
+
+    /// Source of the yield earned by the escrow of surveys.
+    pub trait EscrowYieldSource<SurveyId, Balance> {
+        /// Take the yield accrued by the escrow of a survey, which is then paid out.
+        fn take_accrued_yield(survey_id: SurveyId) -> Balance;
+    }
+
+    /// Escrow earns no yield.
+    impl<SurveyId, Balance: Zero> EscrowYieldSource<SurveyId, Balance> for () {
+        fn take_accrued_yield(_: SurveyId) -> Balance {
+            Zero::zero()
+        }
+    }
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -46,6 +153,157 @@ pub mod pallet {
             + fungible::hold::Mutate<Self::AccountId>
             + fungible::freeze::Inspect<Self::AccountId>
             + fungible::freeze::Mutate<Self::AccountId>;
+
+        /// Id of surveys, such as `u128`, incremented by `create_survey_auto_id`.
+        type SurveyId: Parameter
+            + Member
+            + MaxEncodedLen
+            + Copy
+            + Ord
+            + Default
+            + One
+            + CheckedAdd
+            + Saturating;
+
+        /// Assets in which surveys can pay a bonus on top of the native reward.
+        type BonusAssets: fungibles::Inspect<Self::AccountId> + fungibles::Mutate<Self::AccountId>;
+
+        /// Account paying out the rewards of incentivized calls such as `sweep_expired`.
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
+        /// Reward paid to the caller of `sweep_expired` for every expired survey swept.
+        #[pallet::constant]
+        type SweepReward: Get<BalanceOf<Self>>;
+
+        /// Maximum number of surveys which can be swept in a single `sweep_expired` call.
+        #[pallet::constant]
+        type MaxSweep: Get<u32>;
+
+        /// Maximum weight `on_initialize` spends completing expired surveys, the rest being
+        /// deferred to the next blocks.
+        #[pallet::constant]
+        type MaxExpiryWeight: Get<Weight>;
+
+        /// Maximum number of surveys whose deadline expires in the same block which are completed
+        /// by `on_initialize`, others having to be swept with `sweep_expired`.
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// Maximum number of surveys which can be created in a single `batch_create_surveys` call.
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// Maximum length in bytes of a survey announcement.
+        #[pallet::constant]
+        type MaxAnnouncementLen: Get<u32>;
+
+        /// Minimum amount a survey can be funded with.
+        #[pallet::constant]
+        type MinFunding: Get<BalanceOf<Self>>;
+
+        /// Minimum reward of a participant, lower rewards being considered dust.
+        #[pallet::constant]
+        type MinReward: Get<BalanceOf<Self>>;
+
+        /// Maximum total escrow of all surveys.
+        #[pallet::constant]
+        type MaxTotalEscrow: Get<BalanceOf<Self>>;
+
+        /// Deposit taken from the owner when creating a survey, returned when it is cancelled.
+        #[pallet::constant]
+        type SurveyDeposit: Get<BalanceOf<Self>>;
+
+        /// Fraction of the deposit slashed when a survey is cancelled.
+        #[pallet::constant]
+        type CancellationSlash: Get<Perbill>;
+
+        /// Fraction of every reward paid to `TreasuryAccount` rather than to the participant.
+        #[pallet::constant]
+        type RewardFee: Get<Perbill>;
+
+        /// Account receiving the slashed part of cancelled survey deposits.
+        #[pallet::constant]
+        type SlashDestination: Get<Self::AccountId>;
+
+        /// Highest score an owner can give a participant, earning the full reward.
+        #[pallet::constant]
+        type MaxScore: Get<u8>;
+
+        /// Whether participants without a score earn the full reward rather than nothing.
+        #[pallet::constant]
+        type UnscoredFullReward: Get<bool>;
+
+        /// Maximum reward a single participant can be paid, early bird bonus included.
+        #[pallet::constant]
+        type MaxRewardPerParticipant: Get<BalanceOf<Self>>;
+
+        /// Non-refundable fee charged to the owner when creating a survey.
+        #[pallet::constant]
+        type CreationFee: Get<BalanceOf<Self>>;
+
+        /// Account receiving creation fees. Fees are burnt if `None`.
+        #[pallet::constant]
+        type FeeDestination: Get<Option<Self::AccountId>>;
+
+        /// Whether rewards which would leave a participant below the existential deposit are topped
+        /// up to it out of the survey escrow, creating the account, rather than skipped.
+        #[pallet::constant]
+        type ForceCreateAccounts: Get<bool>;
+
+        /// Whether the participants of a survey are kept in a single set per survey rather than
+        /// one storage entry each, which suits runtimes with small, dense account ids.
+        ///
+        /// Must not change while surveys have registered participants.
+        #[pallet::constant]
+        type DenseParticipants: Get<bool>;
+
+        /// Maximum number of participants of a survey when `DenseParticipants` is set.
+        #[pallet::constant]
+        type MaxDenseParticipants: Get<u32>;
+
+        /// Maximum number of participants removed by a single `clear_participants` call.
+        #[pallet::constant]
+        type MaxClear: Get<u32>;
+
+        /// Number of decimals of the currency rewards are paid in, for display by clients.
+        #[pallet::constant]
+        type Decimals: Get<u8>;
+
+        /// Minimum number of blocks between the funding of a survey and its first reward.
+        #[pallet::constant]
+        type RewardDelay: Get<BlockNumberFor<Self>>;
+
+        /// Number of blocks after a reward during which the owner can claw it back.
+        #[pallet::constant]
+        type ClawbackWindow: Get<BlockNumberFor<Self>>;
+
+        /// Origin allowed to pause and unpause the pallet.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Handler vesting the rewards of surveys configured with `vesting_blocks`.
+        type VestingHandler: RewardVesting<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
+
+        /// Hooks called when surveys are created, funded and completed.
+        type OnSurveyLifecycle: SurveyLifecycleHooks<Self::AccountId, Self::SurveyId>;
+
+        /// Yield earned by the escrow of surveys, shared between participants on completion.
+        type YieldSource: EscrowYieldSource<Self::SurveyId, BalanceOf<Self>>;
+
+        /// Check of the accounts which may be registered to surveys.
+        type ParticipantEligibility: EligibilityCheck<Self::AccountId, Self::SurveyId>;
+
+        /// Balance funders fund surveys with and owners pay their deposits, fees and top-ups with,
+        /// usually `SpendableBalance<Self>`.
+        type FundingSource: FundingSource<Self::AccountId, BalanceOf<Self>>;
+
+        /// Level of the events deposited by the pallet, lower levels suppressing the more verbose
+        /// ones, see `Event::level`.
+        #[pallet::constant]
+        type EventVerbosity: Get<EventLevel>;
+
+        /// Source of randomness ordering the rewards of surveys distributed at random.
+        type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
     }
 
     #[pallet::event]
@@ -53,34 +311,372 @@ pub mod pallet {
     pub enum Event<T: Config> {
         // A new survey is created
         SurveyCreated {
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
+            seq: u64,
             owner_id: OwnerId<T>,
         },
 
         // A survey is funded
         SurveyFunded {
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
+            seq: u64,
             funded_amount: BalanceOf<T>,
             funder_id: FunderId<T>,
         },
 
+        // A survey is created and funded in a single call
+        SurveyCreatedAndFunded {
+            survey_id: T::SurveyId,
+            seq: u64,
+            owner_id: AccountId<T>,
+            funded_amount: BalanceOf<T>,
+        },
+
         // A reward is claimed
         RewardClaimed {
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
+            seq: u64,
             participant_id: ParticipantId<T>,
             reward_amount: BalanceOf<T>,
         },
 
         // A participant is registered as having completed the survey
         NewParticipantRegistered {
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
+            seq: u64,
             participant_id: ParticipantId<T>,
         },
 
         // Status is update for a given survey
         SurveyStatusUpdated {
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
+            seq: u64,
             new_status: Status,
+            /// Stable numeric code of `new_status`, see `Status::code`.
+            status_code: u8,
+            changed_by: AccountId<T>,
+        },
+
+        // A deadline is set for a given survey
+        SurveyDeadlineSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            deadline: BlockNumberFor<T>,
+        },
+
+        // A new owner is proposed for a given survey
+        NewOwnerProposed {
+            survey_id: T::SurveyId,
+            seq: u64,
+            owner_id: OwnerId<T>,
+            proposed_owner_id: OwnerId<T>,
+        },
+
+        // The ownership of a survey is transferred to the proposed owner
+        SurveyOwnershipTransferred {
+            survey_id: T::SurveyId,
+            seq: u64,
+            old_owner_id: OwnerId<T>,
+            new_owner_id: OwnerId<T>,
+        },
+
+        // The vesting period of rewards is set for a given survey
+        SurveyVestingSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            vesting_blocks: Option<BlockNumberFor<T>>,
+        },
+
+        // The pallet is paused or unpaused
+        PalletPauseSet {
+            paused: bool,
+        },
+
+        // The early bird bonus is set for a given survey
+        SurveyEarlyBirdSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            early_bird_count: BalanceOf<T>,
+            early_bird_multiplier: u32,
+        },
+
+        // The participants limit of a survey is reduced
+        ParticipantsLimitReduced {
+            survey_id: T::SurveyId,
+            seq: u64,
+            new_limit: BalanceOf<T>,
+            reclaimable_surplus: BalanceOf<T>,
+        },
+
+        // The reward of a fraudulent participant of a given survey is returned to its escrow
+        RewardClawedBack {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+            amount: BalanceOf<T>,
+            reason_hash: T::Hash,
+        },
+
+        // The owner of a given survey disputes the reward paid to a participant
+        RewardDisputed {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+        },
+
+        // The dispute of a reward is resolved, the reward being clawed back if upheld
+        DisputeResolved {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+            upheld: bool,
+            clawed_back: Option<BalanceOf<T>>,
+        },
+
+        // The owner of a given survey made an announcement
+        SurveyAnnouncement {
+            survey_id: T::SurveyId,
+            seq: u64,
+            message: BoundedVec<u8, T::MaxAnnouncementLen>,
+        },
+
+        // The participants limit of a survey is extended
+        ParticipantsLimitExtended {
+            survey_id: T::SurveyId,
+            seq: u64,
+            new_limit: BalanceOf<T>,
+            reward_amount: Option<BalanceOf<T>>,
+        },
+
+        // Whether extending the participants limit of a given survey re-divides its reward is set
+        SurveyRecomputeOnExtendSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            recompute_reward_on_extend: bool,
+        },
+
+        // The surplus of a survey is reclaimed by its owner
+        SurplusReclaimed {
+            survey_id: T::SurveyId,
+            seq: u64,
+            owner_id: OwnerId<T>,
+            amount: BalanceOf<T>,
+        },
+
+        // Surplus of a given survey is moved to the escrow of another survey of its owner
+        SurplusTransferred {
+            survey_id: T::SurveyId,
+            seq: u64,
+            to_survey: T::SurveyId,
+            amount: BalanceOf<T>,
+        },
+
+        // The reward amount of a given survey is changed by its owner
+        RewardAmountSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            reward_amount: BalanceOf<T>,
+        },
+
+        // Participants of a given survey are rewarded by `batch_reward_silent`
+        BatchRewarded {
+            survey_id: T::SurveyId,
+            seq: u64,
+            count: u32,
+            total: BalanceOf<T>,
+        },
+
+        // The escrow of a given survey is topped up by its owner
+        EscrowToppedUp {
+            survey_id: T::SurveyId,
+            seq: u64,
+            amount: BalanceOf<T>,
+            escrow_remaining: BalanceOf<T>,
+        },
+
+        // The visibility of a survey is set
+        SurveyVisibilitySet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            is_public: bool,
+        },
+
+        // A manager is added to a given survey
+        ManagerAdded {
+            survey_id: T::SurveyId,
+            seq: u64,
+            manager_id: AccountId<T>,
+        },
+
+        // A manager is removed from a given survey
+        ManagerRemoved {
+            survey_id: T::SurveyId,
+            seq: u64,
+            manager_id: AccountId<T>,
+        },
+
+        // A new survey template is created
+        TemplateCreated {
+            owner_id: OwnerId<T>,
+            template_id: TemplateId,
+        },
+
+        // The reward mode of a given survey is set
+        SurveyRewardModeSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            post_hoc_rewards: bool,
+        },
+
+        // The yield earned by the escrow of a given survey is shared between its participants
+        YieldDistributed {
+            survey_id: T::SurveyId,
+            seq: u64,
+            amount: BalanceOf<T>,
+            participants: u32,
+        },
+
+        // The rewards of a given post-hoc survey are paid to all its participants
+        RewardsFinalized {
+            survey_id: T::SurveyId,
+            seq: u64,
+            reward_amount: BalanceOf<T>,
+            rewarded_count: u32,
+        },
+
+        // A survey is cancelled, its escrow and deposit refunded minus the slash
+        SurveyCancelled {
+            survey_id: T::SurveyId,
+            seq: u64,
+            refunded_amount: BalanceOf<T>,
+            slashed_amount: BalanceOf<T>,
+        },
+
+        // The bonus of a given survey is set
+        SurveyBonusSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            bonus: Option<(AssetIdOf<T>, AssetBalanceOf<T>)>,
+        },
+
+        // The referral reward of a given survey is set
+        ReferralRewardSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            referral_reward: BalanceOf<T>,
+        },
+
+        // A referrer is rewarded for the registration of a participant
+        ReferralRewardPaid {
+            survey_id: T::SurveyId,
+            seq: u64,
+            referrer: AccountId<T>,
+            participant_id: ParticipantId<T>,
+            referral_reward: BalanceOf<T>,
+        },
+
+        // A given survey is archived
+        SurveyArchived {
+            survey_id: T::SurveyId,
+            seq: u64,
+        },
+
+        // The owner of a given survey scores a participant
+        ParticipantScoreSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+            score: u8,
+        },
+
+        // The minimum number of participants of a given survey is set
+        SurveyMinParticipantsSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            min_participants: BalanceOf<T>,
+        },
+
+        // Remainder distribution of a given survey is set
+        SurveyRemainderDistributionSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            distribute_remainder: bool,
+        },
+
+        // Distribution order of a given survey is set
+        SurveyDistributionOrderSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            distribution: DistributionOrder,
+        },
+
+        // Reward rounding of a given survey is set
+        SurveyRewardRoundingSet {
+            survey_id: T::SurveyId,
+            seq: u64,
+            rounding: RewardRounding,
+        },
+
+        // A participant withdraws from a given survey
+        ParticipationWithdrawn {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+        },
+
+        // A participant updates the hash of their response to a given survey
+        ResponseHashUpdated {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+            response_hash: T::Hash,
+        },
+
+        // Participants of a given survey are removed
+        ParticipantsCleared {
+            survey_id: T::SurveyId,
+            seq: u64,
+            removed: u32,
+        },
+
+        // A reward is skipped as it would leave the participant below the existential deposit
+        RewardSkippedBelowED {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+            reward_amount: BalanceOf<T>,
+        },
+
+        // A reward could not be paid because of a defensive condition
+        RewardFailedDefensive {
+            survey_id: T::SurveyId,
+            seq: u64,
+            participant_id: ParticipantId<T>,
+        },
+
+        // A survey template is deleted
+        TemplateDeleted {
+            owner_id: OwnerId<T>,
+            template_id: TemplateId,
+        },
+
+        // Rewards are paid to participants of any survey in the current block
+        BlockRewardSummary {
+            total_rewards_paid: BalanceOf<T>,
+            participants_paid: u32,
+        },
+
+        // A survey is completed by `on_initialize` once its deadline has passed
+        SurveyAutoCompleted {
+            survey_id: T::SurveyId,
+            seq: u64,
+        },
+
+        // Expired surveys have been swept and the sweeper rewarded
+        ExpiredSurveysSwept {
+            sweeper: AccountId<T>,
+            swept_count: u32,
+            reward_amount: BalanceOf<T>,
         },
     }
 
@@ -96,6 +692,8 @@ pub mod pallet {
         SurveyNotFunded,
         /// Trying to fund a survey with an amount inferior to participant_limit
         FundingInferiorNumberParticipants,
+        /// Trying to fund a survey with an amount inferior to the configured minimum funding.
+        FundingBelowMinimum,
         /// Trying to claim a reward for a participant who has already claimed their reward.
         ParticipantAlreadyRewarded,
         /// Trying to register a participant_id already registered.
@@ -108,129 +706,727 @@ pub mod pallet {
         NotEnoughBalanceForFunding,
         /// Trying to do operations on a survey while not being its owner.
         NotOwnerOfSurvey,
-        /// Trying to register a participant on an inactive survey
+        /// Trying to do operations on a survey while being neither its owner nor one of its managers.
+        NotAuthorized,
+        /// Trying to remove an account which is not a manager of the survey.
+        ManagerNotFound,
+        /// Trying to register a participant on an inactive survey.
+        /// Fallback for statuses without a dedicated error.
         SurveyIsNotActive,
+        /// Trying to register a participant on a paused survey.
+        SurveyPaused,
+        /// Trying to register a participant on a completed survey.
+        SurveyCompleted,
+        /// Trying to set a deadline which is not in the future.
+        DeadlineInThePast,
+        /// Trying to accept the ownership of a survey without being its proposed owner.
+        NotProposedOwner,
+        /// Trying to use a template which does not exist for the caller.
+        TemplateNotFound,
+        /// Trying to do operations on surveys while the pallet is paused.
+        PalletPaused,
+        /// Trying to set an early bird multiplier of zero.
+        InvalidEarlyBirdMultiplier,
+        /// Trying to fund a survey with an amount which does not cover the early bird bonus.
+        FundingInsufficientForEarlyBird,
+        /// Trying to reduce the participants limit below the number of registered participants.
+        LimitBelowCurrentParticipants,
+        /// Trying to reduce the participants limit to a value which is not lower than the current one.
+        LimitNotReduced,
+        /// Trying to fund a survey with an amount which does not cover its rounded up reward for
+        /// every participant.
+        FundingInsufficientForCeilReward,
+        /// Reward of a participant is below `MinReward`.
+        RewardBelowMinimum,
+        /// Trying to create a survey without any participant slot.
+        ZeroParticipantsLimit,
+        /// Trying to register a participant rejected by `ParticipantEligibility`.
+        ParticipantNotEligible,
+        /// Trying to fund a survey beyond `MaxTotalEscrow` across all surveys.
+        TotalEscrowCapExceeded,
+        /// Trying to claw back the reward of a participant who was not rewarded.
+        ParticipantNotRewarded,
+        /// Trying to claw back a reward after `ClawbackWindow` blocks have passed.
+        ClawbackWindowExpired,
+        /// Participant no longer holds the reward being clawed back, or treasury its fee.
+        ClawbackFailed,
+        /// Trying to dispute a reward which is already disputed.
+        RewardAlreadyDisputed,
+        /// Trying to resolve the dispute of a reward which is not disputed.
+        RewardNotDisputed,
+        /// Trying to extend the participants limit to a value which is not higher than the current one.
+        LimitNotExtended,
+        /// Trying to re-divide the reward of a survey which already rewarded participants.
+        CannotRecomputeAfterRewards,
+        /// Trying to reclaim the surplus of a survey which has none.
+        NoSurplusToReclaim,
+        /// Trying to transfer more than the surplus of a survey.
+        AmountExceedsSurplus,
+        /// Trying to transfer the surplus of a survey to itself.
+        CannotTransferToSameSurvey,
+        /// Trying to transfer the surplus of a survey which is not completed or cancelled.
+        SurveyNotEnded,
         /// Defensive Error: While trying to claim a reward for a participant, survey has not enough funds.
         DefensiveNotEnoughFundsInSurveyForReward,
         /// Defensive Error: Error when dividing for reward computation
         DefensiveErrorWhenDividing,
         /// Defensive Error: An overflow occured when the operation was supposed to be safe
         DefensiveUnexpectedOverflow,
+        /// Defensive Error: A funded survey has no reward amount
+        DefensiveRewardAmountMissing,
+        /// Trying to fund a survey whose committed rewards exceed its funding.
+        RewardExceedsFunding,
+        /// Trying to reward a single participant of a survey whose rewards are computed post-hoc.
+        RewardsDeferred,
+        /// Trying to finalize the rewards of a survey which does not compute them post-hoc.
+        NotPostHocSurvey,
+        /// Trying to finalize the rewards of a survey which have already been finalized.
+        RewardsAlreadyFinalized,
+        /// Trying to finalize the rewards of a survey without any registered participant.
+        NoParticipantsToReward,
+        /// Owner does not have enough balance for the creation deposit.
+        NotEnoughBalanceForDeposit,
+        /// Trying to do operations on a survey which has been cancelled.
+        SurveyCancelled,
+        /// Trying to register the zero account as participant.
+        InvalidParticipant,
+        /// Trying to clear the participants of a survey which already rewarded some.
+        CannotClearAfterRewards,
+        /// Trying to register a participant after the deadline of the survey.
+        SurveyExpired,
+        /// Trying to reward before the survey has `min_participants` participants.
+        MinimumParticipantsNotMet,
+        /// Minimum number of participants is above the participants limit.
+        MinParticipantsAboveLimit,
+        /// Score is above `MaxScore`.
+        InvalidScore,
+        /// Reward is above `MaxRewardPerParticipant`.
+        RewardExceedsPolicyCap,
+        /// Trying to change the reward of a survey which already rewarded participants.
+        RewardAlreadyPaid,
+        /// Owner does not have enough balance for the creation fee.
+        CannotAffordCreationFee,
+        /// Trying to archive a survey which is not completed or cancelled, or still holds escrow.
+        SurveyNotSettled,
+        /// The same participant appears several times in a batch.
+        DuplicateInBatch,
+        /// Trying to reward before `RewardDelay` blocks have passed since funding.
+        RewardTooEarly,
+        /// Funder does not have enough of the bonus asset for funding.
+        NotEnoughBonusForFunding,
+        /// Referrer is not a registered participant of the survey, or is the participant itself.
+        InvalidReferrer,
+        /// Funding does not cover the referral reward of every participant.
+        FundingInsufficientForReferrals,
+        /// Trying to cancel a survey through its status rather than `cancel_survey`.
+        CannotSetCancelledStatus,
+        /// Balance released by a survey cannot be minted to its recipient.
+        PayoutFailed,
     }
 
     // STRUCTS & ENUMS
+    /// Order in which `distribute_rewards` pays the participants of a survey.
+    #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    pub enum DistributionOrder {
+        /// Registration order.
+        Fifo,
+        /// Order drawn from `Config::Randomness`.
+        Random,
+    }
+
+    /// Verbosity of the events deposited by the pallet, from the least to the most verbose.
+    #[derive(
+        Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo, MaxEncodedLen, Debug,
+    )]
+    pub enum EventLevel {
+        /// Lifecycle events of surveys only.
+        Minimal,
+        /// Lifecycle events, settings changes and per-survey or per-block aggregates.
+        Summary,
+        /// Every event, including per-participant registrations and rewards.
+        Full,
+    }
+
+    impl<T: Config> Event<T> {
+        /// Lowest `Config::EventVerbosity` at which the event is deposited.
+        pub fn level(&self) -> EventLevel {
+            match self {
+                Event::SurveyCreated { .. } |
+                Event::SurveyFunded { .. } |
+                Event::SurveyCreatedAndFunded { .. } |
+                Event::SurveyStatusUpdated { .. } |
+                Event::SurveyOwnershipTransferred { .. } |
+                Event::SurveyAutoCompleted { .. } |
+                Event::SurveyCancelled { .. } |
+                Event::SurveyArchived { .. } |
+                Event::RewardsFinalized { .. } |
+                Event::PalletPauseSet { .. } => EventLevel::Minimal,
+                Event::NewParticipantRegistered { .. } |
+                Event::RewardClaimed { .. } |
+                Event::RewardClawedBack { .. } |
+                Event::RewardDisputed { .. } |
+                Event::DisputeResolved { .. } |
+                Event::ReferralRewardPaid { .. } |
+                Event::ParticipantScoreSet { .. } |
+                Event::ParticipationWithdrawn { .. } |
+                Event::ResponseHashUpdated { .. } |
+                Event::RewardSkippedBelowED { .. } |
+                Event::RewardFailedDefensive { .. } => EventLevel::Full,
+                _ => EventLevel::Summary,
+            }
+        }
+    }
+
+    /// Rounding of the reward of a survey when its funding does not divide evenly.
+    #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    pub enum RewardRounding {
+        /// Round down, leaving the remainder in escrow.
+        Floor,
+        /// Round up, requiring funding for the rounded up reward of every participant.
+        Ceil,
+    }
+
+    /// Calls a survey was created and funded with.
+    #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    pub enum CreationFlow {
+        /// Created, then funded in a separate call.
+        Separate,
+        /// Created and funded by `create_and_fund_survey`.
+        Combined,
+    }
+
     #[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
     pub enum Status {
         Active,
         Paused,
         Completed,
+        Cancelled,
+    }
+
+    impl Status {
+        /// Numeric code of the status, stable across reorderings of the variants.
+        pub fn code(&self) -> u8 {
+            match self {
+                Status::Active => 1,
+                Status::Paused => 2,
+                Status::Completed => 3,
+                Status::Cancelled => 4,
+            }
+        }
     }
 
     #[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
     #[scale_info(skip_type_params(T))]
     pub struct Survey<T: Config> {
-        pub survey_id: SurveyId,
+        pub survey_id: T::SurveyId,
         pub owner_id: OwnerId<T>,
         pub participants_limit: BalanceOf<T>,
         pub number_participants: BalanceOf<T>,
+        /// Number of participants who have been rewarded.
+        pub rewarded_count: BalanceOf<T>,
         pub is_funded: bool,
         pub funded_amount: Option<BalanceOf<T>>,
         pub reward_amount: Option<BalanceOf<T>>,
         pub status: Status,
+        pub deadline: Option<BlockNumberFor<T>>,
+        /// Number of blocks over which rewards are vested. Rewards are paid immediately if `None`.
+        pub vesting_blocks: Option<BlockNumberFor<T>>,
+        /// Number of first registered participants earning `reward_amount * early_bird_multiplier`.
+        pub early_bird_count: BalanceOf<T>,
+        pub early_bird_multiplier: u32,
+        /// Funds freed by reducing `participants_limit`, which the owner can reclaim.
+        pub reclaimable_surplus: BalanceOf<T>,
+        /// Private surveys are skipped by discovery queries.
+        pub is_public: bool,
+        pub funder_id: Option<FunderId<T>>,
+        /// Block at which the survey was funded.
+        pub funded_at: Option<BlockNumberFor<T>>,
+        /// Block at which the survey was last completed or cancelled.
+        pub completed_at: Option<BlockNumberFor<T>>,
+        /// Account which last changed the status of the survey.
+        pub status_changed_by: Option<AccountId<T>>,
+        /// Total paid out to participants, referrers and the treasury.
+        pub total_paid: BalanceOf<T>,
+        /// Deposit taken from the owner at creation.
+        pub deposit: BalanceOf<T>,
+        /// Asset and amount paid to every participant on top of `reward_amount`.
+        pub bonus: Option<(AssetIdOf<T>, AssetBalanceOf<T>)>,
+        /// Bonus funds of the survey which have not been paid out or refunded yet.
+        pub bonus_escrow: AssetBalanceOf<T>,
+        /// Funds of the survey which have not been paid out or reclaimed yet.
+        pub escrow_remaining: BalanceOf<T>,
+        /// Rewards are computed from the actual registrations by `finalize_rewards` rather than
+        /// from `participants_limit` at funding.
+        pub post_hoc_rewards: bool,
+        /// Paid from escrow to the referrer of every participant registered with
+        /// `register_with_referrer`.
+        pub referral_reward: BalanceOf<T>,
+        /// Number of participants below which rewards cannot be paid.
+        pub min_participants: BalanceOf<T>,
+        /// The remainder of dividing the funding between participants is paid out, one unit to
+        /// each of the first registered participants, rather than left in escrow.
+        pub distribute_remainder: bool,
+        /// Number of first registered participants earning `reward_amount + 1`.
+        pub reward_remainder: BalanceOf<T>,
+        /// Order in which `distribute_rewards` pays participants.
+        pub distribution: DistributionOrder,
+        /// Extending `participants_limit` re-divides the rewards between the new slots rather than
+        /// requiring funding for them.
+        pub recompute_reward_on_extend: bool,
+        /// Rounding of `reward_amount`.
+        pub rounding: RewardRounding,
+        /// Whether the survey was created and funded in a single call.
+        pub creation_flow: CreationFlow,
         // created_at ?
     }
 
+    #[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SurveyTemplate<T: Config> {
+        pub participants_limit: BalanceOf<T>,
+        /// Number of blocks after creation at which the instantiated survey's deadline is set.
+        pub duration: Option<BlockNumberFor<T>>,
+    }
+
+    /// Summary kept for a survey once `archive_survey` freed its storage.
+    #[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ArchivedSurvey<T: Config> {
+        pub owner: OwnerId<T>,
+        pub total_participants: BalanceOf<T>,
+        pub total_paid: BalanceOf<T>,
+        pub completed_at: Option<BlockNumberFor<T>>,
+    }
+
+    /// Participant counts and escrow of a survey, as returned by `reward_breakdown`.
+    #[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    pub struct RewardBreakdown<Balance> {
+        pub registered: Balance,
+        pub rewarded: Balance,
+        /// Registered participants not rewarded yet.
+        pub pending: Balance,
+        pub escrow_remaining: Balance,
+    }
+
+    /// Reward paid to a participant, as returned by `reward_receipt`.
+    #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+    pub struct RewardReceipt<Balance, BlockNumber> {
+        /// Amount paid to the participant.
+        pub amount: Balance,
+        /// Cut of `RewardFee` paid to the treasury, escrow having paid `amount + fee`.
+        pub fee: Balance,
+        /// Block at which the participant was paid.
+        pub block: BlockNumber,
+    }
+
     // STORAGE UNITS
     #[pallet::storage]
     #[pallet::getter(fn get_survey)]
     /// StorageMap which stores every survey created.
     ///
     /// Types:
-    ///     Key: [`SurveyId`]
+    ///     Key: [`T::SurveyId`]
     ///     Value: [`Survey<T>`]
-    pub type SurveysMap<T: Config> = StorageMap<_, Blake2_128Concat, SurveyId, Survey<T>>;
+    pub type SurveysMap<T: Config> = StorageMap<_, Blake2_128Concat, T::SurveyId, Survey<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_archived_survey)]
+    /// StorageMap which stores the summary of every archived survey.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`ArchivedSurvey<T>`]
+    pub type Archive<T: Config> = StorageMap<_, Blake2_128Concat, T::SurveyId, ArchivedSurvey<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn finalization_cursor)]
+    /// StorageMap which stores for every survey whose `finalize_rewards` has participants left to
+    /// pay the last participant read, the next call resuming after it.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`ParticipantId<T>`]
+    pub type FinalizationCursor<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::SurveyId, ParticipantId<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn is_archival_pending)]
+    /// StorageMap which stores the archived surveys whose participants and entries are not all
+    /// removed yet, `archive_survey` having to be called again for them.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`bool`]
+    pub type ArchivalPending<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::SurveyId, bool, ValueQuery>;
 
     #[pallet::storage]
-    #[pallet::getter(fn is_participant)]
-    /// StorageDoubleMap which stores for every survey the participants who submitted an answer.
+    /// StorageDoubleMap which stores for every survey the participants who submitted an answer,
+    /// unless `DenseParticipants` is set.
     ///
     /// Types:
-    ///     Key1: [`SurveyId`]
+    ///     Key1: [`T::SurveyId`]
     ///     Key2: [`ParticipantId<T>`]
     ///     Value: [`bool`]
     pub type Participants<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        SurveyId,
+        T::SurveyId,
         Blake2_128Concat,
         ParticipantId<T>,
         bool,
         ValueQuery,
     >;
 
+    #[pallet::storage]
+    /// StorageMap which stores for every survey the set of participants who submitted an answer,
+    /// if `DenseParticipants` is set.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`BoundedBTreeSet<ParticipantId<T>, T::MaxDenseParticipants>`]
+    pub type ParticipantSets<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::SurveyId,
+        BoundedBTreeSet<ParticipantId<T>, T::MaxDenseParticipants>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn is_participant_already_rewarded)]
     /// StorageDoubleMap which stores for every survey the participants who are already rewarded.
     ///
     /// Types:
-    ///     Key1: [`SurveyId`]
+    ///     Key1: [`T::SurveyId`]
     ///     Key2: [`ParticipantId<T>`]
     ///     Value: [`bool`]
     pub type ParticipantsRewarded<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        SurveyId,
+        T::SurveyId,
         Blake2_128Concat,
         ParticipantId<T>,
         bool,
         ValueQuery,
     >;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Create a new survey
-        ///
-        /// - `survey_id`: The off-chain computed unique id of the survey
-        /// - `participants_limmit`: The max number of participants for this survey
-        ///
-        /// REQUIRES: Survey must not have been crated already
-        ///
-        /// Emits `SurveyCreated`
-        #[pallet::call_index(0)]
-        #[pallet::weight(u64::default())]
-        pub fn create_survey(
-            origin: OriginFor<T>,
-            survey_id: SurveyId,
-            participants_limit: BalanceOf<T>,
-        ) -> DispatchResult {
-            let owner_id = ensure_signed(origin)?;
-
-            // Check if survey is not already created
-            ensure!(
-                SurveysMap::<T>::get(survey_id).is_none(),
-                Error::<T>::SurveyAlreadyCreated
-            );
+    #[pallet::storage]
+    #[pallet::getter(fn cached_reward_amount)]
+    /// StorageMap which mirrors the `reward_amount` of every survey whose reward is known, so that
+    /// rewarding participants does not require decoding it from the full survey.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`BalanceOf<T>`]
+    pub type RewardAmounts<T: Config> = StorageMap<_, Blake2_128Concat, T::SurveyId, BalanceOf<T>>;
 
-            // Create the survey
-            let new_survey = Survey {
-                survey_id,
-                owner_id: owner_id.clone(),
-                participants_limit,
-                number_participants: 0u32.into(),
-                is_funded: false,
-                funded_amount: None,
-                reward_amount: None,
-                status: Status::Active,
-            };
+    #[pallet::storage]
+    #[pallet::getter(fn registration_index)]
+    /// StorageDoubleMap which stores for every survey the registration order of its participants, starting at 0.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`ParticipantId<T>`]
+    ///     Value: [`BalanceOf<T>`]
+    pub type RegistrationIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::SurveyId,
+        Blake2_128Concat,
+        ParticipantId<T>,
+        BalanceOf<T>,
+    >;
 
-            SurveysMap::<T>::insert(survey_id, new_survey);
+    #[pallet::storage]
+    #[pallet::getter(fn registration_count)]
+    /// StorageMap which stores for every survey the number of registrations it ever had, which is
+    /// the `RegistrationIndex` of its next participant.
+    ///
+    /// Unlike `number_participants`, it is not decremented when participants leave, so that no
+    /// registration index is ever given twice.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`BalanceOf<T>`]
+    pub type RegistrationCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::SurveyId, BalanceOf<T>, ValueQuery>;
 
-            Self::deposit_event(Event::SurveyCreated {
-                survey_id,
-                owner_id,
-            });
+    #[pallet::storage]
+    #[pallet::getter(fn lifetime_rewards)]
+    /// StorageMap which stores for every participant the total amount of rewards received across all surveys.
+    ///
+    /// Types:
+    ///     Key: [`ParticipantId<T>`]
+    ///     Value: [`BalanceOf<T>`]
+    pub type LifetimeRewards<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParticipantId<T>, BalanceOf<T>, ValueQuery>;
 
-            Ok(())
+    #[pallet::storage]
+    #[pallet::getter(fn reward_receipt)]
+    /// StorageDoubleMap which stores for every survey a receipt of the reward paid to each
+    /// rewarded participant, proving the payment and bounding clawbacks.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`ParticipantId<T>`]
+    ///     Value: [`RewardReceipt<BalanceOf<T>, BlockNumberFor<T>>`]
+    pub type RewardReceipts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::SurveyId,
+        Blake2_128Concat,
+        ParticipantId<T>,
+        RewardReceipt<BalanceOf<T>, BlockNumberFor<T>>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn disputed_reward)]
+    /// StorageDoubleMap which stores for every survey the block at which the owner disputed the
+    /// reward of a participant, until the dispute is resolved.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`ParticipantId<T>`]
+    ///     Value: [`BlockNumberFor<T>`]
+    pub type DisputedRewards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::SurveyId,
+        Blake2_128Concat,
+        ParticipantId<T>,
+        BlockNumberFor<T>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_owner)]
+    /// StorageMap which stores for every survey the account proposed as its next owner.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`OwnerId<T>`]
+    pub type PendingOwners<T: Config> = StorageMap<_, Blake2_128Concat, T::SurveyId, OwnerId<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_template)]
+    /// StorageDoubleMap which stores for every owner the survey templates they created.
+    ///
+    /// Types:
+    ///     Key1: [`OwnerId<T>`]
+    ///     Key2: [`TemplateId`]
+    ///     Value: [`SurveyTemplate<T>`]
+    pub type Templates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        OwnerId<T>,
+        Blake2_128Concat,
+        TemplateId,
+        SurveyTemplate<T>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn participant_score)]
+    /// StorageDoubleMap which stores for every survey the scores given by the owner to participants.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`ParticipantId<T>`]
+    ///     Value: [`u8`]
+    pub type ParticipantScores<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::SurveyId, Blake2_128Concat, ParticipantId<T>, u8>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn participant_response)]
+    /// StorageDoubleMap which stores for every survey the hashes of the responses of participants.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`ParticipantId<T>`]
+    ///     Value: [`T::Hash`]
+    pub type ParticipantResponses<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::SurveyId,
+        Blake2_128Concat,
+        ParticipantId<T>,
+        T::Hash,
+    >;
+
+    #[pallet::storage]
+    /// StorageDoubleMap which stores for every participant the surveys they are registered to.
+    ///
+    /// Types:
+    ///     Key1: [`ParticipantId<T>`]
+    ///     Key2: [`T::SurveyId`]
+    ///     Value: [`()`]
+    pub type JoinedSurveys<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, ParticipantId<T>, Blake2_128Concat, T::SurveyId, ()>;
+
+    #[pallet::storage]
+    /// StorageDoubleMap which stores for every survey the accounts managing it on behalf of its owner.
+    ///
+    /// Types:
+    ///     Key1: [`T::SurveyId`]
+    ///     Key2: [`AccountId<T>`]
+    ///     Value: [`()`]
+    pub type Managers<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::SurveyId, Blake2_128Concat, AccountId<T>, ()>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn event_seq)]
+    /// StorageMap which stores for every survey the sequence number of its next event.
+    ///
+    /// Every event about a survey carries its `seq`, incremented by one from event to event, so
+    /// that indexers can deduplicate events and detect missing ones.
+    ///
+    /// Types:
+    ///     Key: [`T::SurveyId`]
+    ///     Value: [`u64`]
+    pub type EventSeq<T: Config> = StorageMap<_, Blake2_128Concat, T::SurveyId, u64, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn is_paused)]
+    /// StorageValue which stores whether the pallet is paused.
+    ///
+    /// Types:
+    ///     Value: [`bool`]
+    pub type PalletPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::storage]
+    /// StorageValue which stores the id of the next template to be created.
+    ///
+    /// Types:
+    ///     Value: [`TemplateId`]
+    pub type NextTemplateId<T: Config> = StorageValue<_, TemplateId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_survey_id)]
+    /// StorageValue which stores the lowest id `create_survey_auto_id` may use.
+    ///
+    /// It is kept above every created survey id, manual ids included.
+    ///
+    /// Types:
+    ///     Value: [`T::SurveyId`]
+    pub type NextSurveyId<T: Config> = StorageValue<_, T::SurveyId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn total_surveys)]
+    /// StorageValue which stores the number of surveys in `SurveysMap`, archived surveys excluded.
+    ///
+    /// Types:
+    ///     Value: [`u64`]
+    pub type SurveyCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    #[pallet::storage]
+    /// StorageValue which stores the total of the rewards paid in the current block, and the
+    /// number of participants paid, reset at the end of every block.
+    ///
+    /// Types:
+    ///     Value: ([`BalanceOf<T>`], [`u32`])
+    pub type BlockRewards<T: Config> = StorageValue<_, (BalanceOf<T>, u32), ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn total_escrow)]
+    /// StorageValue which stores the sum of the escrow of every survey.
+    ///
+    /// Types:
+    ///     Value: [`BalanceOf<T>`]
+    pub type TotalEscrow<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    #[pallet::storage]
+    /// StorageMap which stores the surveys to complete in a block, their deadline having passed.
+    ///
+    /// Entries are not removed when a deadline changes, surveys being checked when processed.
+    ///
+    /// Types:
+    ///     Key: [`BlockNumberFor<T>`]
+    ///     Value: [`BoundedVec<T::SurveyId, T::MaxExpiriesPerBlock>`]
+    pub type SurveyExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<T::SurveyId, T::MaxExpiriesPerBlock>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    /// StorageValue which stores the first block whose `SurveyExpiries` are not all processed.
+    ///
+    /// Types:
+    ///     Value: [`BlockNumberFor<T>`]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::process_expiries(n, T::MaxExpiryWeight::get())
+        }
+
+        fn on_finalize(_n: BlockNumberFor<T>) {
+            let (total_rewards_paid, participants_paid) = BlockRewards::<T>::take();
+            if participants_paid > 0 {
+                Self::emit_event(Event::BlockRewardSummary {
+                    total_rewards_paid,
+                    participants_paid,
+                });
+            }
+        }
+
+        fn integrity_test() {
+            // Every bound is a constant of the metadata, and none of them can be zero
+            assert!(T::MaxBatchSize::get() > 0, "`MaxBatchSize` must not be zero");
+            assert!(T::MaxSweep::get() > 0, "`MaxSweep` must not be zero");
+            assert!(T::MaxClear::get() > 0, "`MaxClear` must not be zero");
+            assert!(T::MaxAnnouncementLen::get() > 0, "`MaxAnnouncementLen` must not be zero");
+            assert!(T::MaxDenseParticipants::get() > 0, "`MaxDenseParticipants` must not be zero");
+            assert!(T::MaxExpiriesPerBlock::get() > 0, "`MaxExpiriesPerBlock` must not be zero");
+            // Scores are divided by `MaxScore`
+            assert!(T::MaxScore::get() > 0, "`MaxScore` must not be zero");
+            // Otherwise no reward could ever be paid
+            assert!(
+                T::MinReward::get() <= T::MaxRewardPerParticipant::get(),
+                "`MinReward` must not exceed `MaxRewardPerParticipant`"
+            );
+            assert!(
+                T::MinFunding::get() <= T::MaxTotalEscrow::get(),
+                "`MinFunding` must not exceed `MaxTotalEscrow`"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new survey
+        ///
+        /// - `survey_id`: The off-chain computed unique id of the survey
+        /// - `participants_limmit`: The max number of participants for this survey
+        ///
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Survey must not have been crated already
+        /// REQUIRES: Participants limit should not be zero.
+        ///
+        /// Emits `SurveyCreated`
+        #[pallet::call_index(0)]
+        #[pallet::weight(u64::default())]
+        pub fn create_survey(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participants_limit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "create_survey: survey_id = {:?}, caller = {:?}, participants_limit = {:?}",
+                survey_id, owner_id, participants_limit
+            );
+
+            Self::do_create_survey(owner_id, survey_id, participants_limit, None)
         }
 
         /// Fund an existing survey
@@ -239,7 +1435,9 @@ pub mod pallet {
         /// - `fund_amount`: the amount the owner is willing to fund the survey
         ///
         /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
         /// REQUIRES: Survey should not be already funded.
+        /// REQUIRES: Survey should be active, a cancelled survey never refunding its escrow.
         /// REQUIRES: Owner should have enough free balance.
         /// REQUIRES: Can only be called by survey owner.
         ///
@@ -248,74 +1446,51 @@ pub mod pallet {
         #[pallet::weight(u64::default())]
         pub fn fund_survey(
             origin: OriginFor<T>,
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
             fund_amount: BalanceOf<T>,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
-            let survey_option = SurveysMap::<T>::get(survey_id);
-
-            // Check that survey is created
-            match survey_option {
-                None => Err(Error::<T>::SurveyNotCreated.into()),
-                Some(survey) => {
-                    // Check that caller is owner
-                    ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
-
-                    // Check that survey is not already funded
-                    ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
-
-                    // Check that funding amount is superior to participants_limit (otherwise reward_amount will be equal to 0)
-                    ensure!(
-                        survey.participants_limit <= fund_amount,
-                        Error::<T>::FundingInferiorNumberParticipants
-                    );
+            log::debug!(
+                target: LOG_TARGET,
+                "fund_survey: survey_id = {:?}, caller = {:?}, fund_amount = {:?}",
+                survey_id, caller, fund_amount
+            );
 
-                    // Check that owner has enough balance for funding
-                    let owner_balance: BalanceOf<T> =
-                        <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(
-                            &survey.owner_id,
-                        );
-                    let new_owner_balance = owner_balance
-                        .checked_sub(&fund_amount)
-                        .ok_or(Error::<T>::NotEnoughBalanceForFunding)?;
-
-                    // Update owner balance
-                    let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
-                        &survey.owner_id,
-                        new_owner_balance,
-                    );
+            Self::do_fund_survey(caller, survey_id, fund_amount, true)
+        }
 
-                    // Compute reward amount
-                    let reward_amount = fund_amount
-                        .checked_div(&survey.participants_limit)
-                        .ok_or(Error::<T>::DefensiveErrorWhenDividing)
-                        .map_err(|e| {
-                            #[cfg(test)]
-                            panic!("defensive error happened: {:?}", e);
-
-                            log::error!(target: "..", "defensive error happened: {:?}", e);
-                            e
-                        })?;
-
-                    // Fund survey
-                    let funded_survey = Survey {
-                        is_funded: true,
-                        funded_amount: Some(fund_amount),
-                        reward_amount: Some(reward_amount),
-                        ..survey
-                    };
-                    SurveysMap::<T>::insert(survey_id, funded_survey);
+        /// Fund an existing survey of another owner
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `fund_amount`: the amount the caller is willing to fund the survey
+        ///
+        /// The caller is recorded as funder and receives the escrow back when it is refunded.
+        /// Rewarding and managing the survey remain restricted to its owner.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Survey should not be already funded.
+        /// REQUIRES: Survey should be active.
+        /// REQUIRES: Caller should have enough free balance.
+        ///
+        /// Emits `SurveyFunded`
+        #[pallet::call_index(42)]
+        #[pallet::weight(u64::default())]
+        pub fn fund_survey_as(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            fund_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-                    Self::deposit_event(Event::SurveyFunded {
-                        survey_id,
-                        funded_amount: fund_amount,
-                        funder_id: caller,
-                    });
+            log::debug!(
+                target: LOG_TARGET,
+                "fund_survey_as: survey_id = {:?}, caller = {:?}, fund_amount = {:?}",
+                survey_id, caller, fund_amount
+            );
 
-                    Ok(())
-                }
-            }
+            Self::do_fund_survey(caller, survey_id, fund_amount, false)
         }
 
         /// Create a survey and fund it
@@ -325,22 +1500,44 @@ pub mod pallet {
         /// - `fund_amount`: the amount the owner is willing to fund the survey
         ///
         /// REQUIRES: Survey must not have been crated already
+        /// REQUIRES: Participants limit should not be zero.
         /// REQUIRES: Survey has to be created already.
         /// REQUIRES: Survey should not be already funded.
         /// REQUIRES: Owner should have enough free balance.
         /// REQUIRES: Can only be called by survey owner.
         ///
-        /// Emits `SurveyCreated`, `SurveyFunded`
+        /// Emits `SurveyCreated`, `SurveyFunded`, `SurveyCreatedAndFunded`
         #[pallet::call_index(2)]
         #[pallet::weight(u64::default())]
         pub fn create_and_fund_survey(
             origin: OriginFor<T>,
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
             participants_limit: BalanceOf<T>,
             fund_amount: BalanceOf<T>,
         ) -> DispatchResult {
-            Self::create_survey(origin.clone(), survey_id, participants_limit)?;
-            Self::fund_survey(origin, survey_id, fund_amount)?;
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "create_and_fund_survey: survey_id = {:?}, caller = {:?}, participants_limit = {:?}, fund_amount = {:?}",
+                survey_id, owner_id, participants_limit, fund_amount
+            );
+
+            Self::do_create_survey(owner_id.clone(), survey_id, participants_limit, None)?;
+            SurveysMap::<T>::mutate(survey_id, |maybe_survey| {
+                if let Some(survey) = maybe_survey {
+                    survey.creation_flow = CreationFlow::Combined;
+                }
+            });
+            Self::do_fund_survey(owner_id.clone(), survey_id, fund_amount, true)?;
+
+            Self::emit_event(Event::SurveyCreatedAndFunded {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                owner_id,
+                funded_amount: fund_amount,
+            });
+
             Ok(())
         }
 
@@ -350,152 +1547,566 @@ pub mod pallet {
         /// - `participant_id`: the address of the participant
         ///
         /// REQUIRES: Survey has to be created already.
-        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: Survey should be funded and active.
+        /// REQUIRES: Deadline of the survey should not have passed.
+        /// REQUIRES: Survey should not be full.
+        /// REQUIRES: Participant should not be the zero account.
         /// REQUIRES: Participant should not be already registered.
+        /// REQUIRES: Participant should be eligible per `ParticipantEligibility`.
         ///
         /// Emits `NewParticipantRegistered`
         #[pallet::call_index(3)]
         #[pallet::weight(u64::default())]
         pub fn register_participant(
             origin: OriginFor<T>,
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
             participant_id: ParticipantId<T>,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
-            let survey_option = SurveysMap::<T>::get(survey_id);
-
-            // Check that survey is created
-            match survey_option {
-                None => Err(Error::<T>::SurveyNotCreated.into()),
-                Some(survey) => {
-                    // Check that caller is owner
-                    ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
-
-                    // Check that survey is already funded
-                    ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
-
-                    // Check that participant is not already registered
-                    ensure!(
-                        !Self::is_participant(survey_id, participant_id.clone()),
-                        Error::<T>::ParticipantAlreadyRegistered
-                    );
-
-                    // Check that we have not reached max number of participants already
-                    ensure!(
-                        survey.number_participants < survey.participants_limit,
-                        Error::<T>::MaxNumberOfParticipantsReached
-                    );
-
-                    // Check that the survey is active
-                    ensure!(
-                        survey.status == Status::Active,
-                        Error::<T>::SurveyIsNotActive
-                    );
-
-                    // Update participants storage unit
-                    Participants::<T>::insert(survey_id, participant_id.clone(), true);
-
-                    // Update number of participants
-                    let number_participants = survey.number_participants + 1u32.into();
-
-                    // Update number of participant on survey
-                    let updated_survey = Survey {
-                        number_participants,
-                        ..survey
-                    };
-                    SurveysMap::<T>::insert(survey_id, updated_survey);
-
-                    Self::deposit_event(Event::NewParticipantRegistered {
-                        survey_id,
-                        participant_id,
-                    });
+            log::debug!(
+                target: LOG_TARGET,
+                "register_participant: survey_id = {:?}, caller = {:?}, participant_id = {:?}",
+                survey_id, caller, participant_id
+            );
 
-                    Ok(())
-                }
-            }
+            Self::do_register_participant(caller, survey_id, participant_id)
         }
 
-        /// Claim reward on behalf of participant and update its balance
+        /// Score a participant of a survey, scaling their reward
         ///
         /// - `survey_id`: the off-chain computed unique id of the survey
         /// - `participant_id`: the address of the participant
+        /// - `score`: the score of the participant, out of `MaxScore`
+        ///
+        /// The participant earns `reward * score / MaxScore`.
         ///
         /// REQUIRES: Survey has to be created already.
         /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Score should not be above `MaxScore`.
         /// REQUIRES: Participant should already be registered.
-        /// REQUIRES: Reward should not have already been claimed.
+        /// REQUIRES: Participant should not have been rewarded.
         ///
-        /// Emits `RewardClaimed`
-        #[pallet::call_index(4)]
+        /// Emits `ParticipantScoreSet`
+        #[pallet::call_index(37)]
         #[pallet::weight(u64::default())]
-        pub fn reward_participant(
+        pub fn set_participant_score(
             origin: OriginFor<T>,
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
             participant_id: ParticipantId<T>,
+            score: u8,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
-            let survey_option = SurveysMap::<T>::get(survey_id);
+            log::debug!(
+                target: LOG_TARGET,
+                "set_participant_score: survey_id = {:?}, caller = {:?}, participant_id = {:?}, score = {}",
+                survey_id, caller, participant_id, score
+            );
 
-            // Check that survey is created
-            match survey_option {
-                None => Err(Error::<T>::SurveyNotCreated.into()),
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(score <= T::MaxScore::get(), Error::<T>::InvalidScore);
+            ensure!(
+                Self::is_participant(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantNotRegistered
+            );
+            ensure!(
+                !Self::is_participant_already_rewarded(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantAlreadyRewarded
+            );
+
+            ParticipantScores::<T>::insert(survey_id, &participant_id, score);
+
+            Self::emit_event(Event::ParticipantScoreSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+                score,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw the caller from a survey they registered to
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// The slot of the caller is freed for another participant. This and
+        /// `update_response_hash` are the only calls a participant signs, which keyless
+        /// participants such as multisigs do through their usual dispatch path.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Survey should not be completed or cancelled.
+        /// REQUIRES: Caller should be registered to the survey.
+        /// REQUIRES: Caller should not have been rewarded.
+        ///
+        /// Emits `ParticipationWithdrawn`
+        #[pallet::call_index(34)]
+        #[pallet::weight(u64::default())]
+        pub fn withdraw_participation(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let participant_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "withdraw_participation: survey_id = {:?}, participant_id = {:?}",
+                survey_id, participant_id
+            );
+
+            Self::mutate_survey(survey_id, |survey| {
+                // Check that survey is still open, its participants being settled otherwise
+                ensure!(survey.status != Status::Completed, Error::<T>::SurveyCompleted);
+                ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+                ensure!(
+                    Self::is_participant(survey_id, participant_id.clone()),
+                    Error::<T>::ParticipantNotRegistered
+                );
+                ensure!(
+                    !Self::is_participant_already_rewarded(survey_id, participant_id.clone()),
+                    Error::<T>::ParticipantAlreadyRewarded
+                );
+
+                Self::remove_participant(survey_id, &participant_id);
+                JoinedSurveys::<T>::remove(&participant_id, survey_id);
+                ParticipantScores::<T>::remove(survey_id, &participant_id);
+                ParticipantResponses::<T>::remove(survey_id, &participant_id);
+                RegistrationIndex::<T>::remove(survey_id, &participant_id);
+                survey.number_participants =
+                    survey.number_participants.saturating_sub(1u32.into());
+
+                Ok(())
+            })?;
+
+            Self::emit_event(Event::ParticipationWithdrawn {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+            });
+
+            Ok(())
+        }
+
+        /// Remove the participants of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// At most `MaxClear` participants are read and removed per call: the call has to be
+        /// repeated until `number_participants` is back to 0 for surveys with more participants.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: No participant should have been rewarded.
+        ///
+        /// Emits `ParticipantsCleared`
+        #[pallet::call_index(26)]
+        #[pallet::weight(Pallet::<T>::clear_participants_weight())]
+        pub fn clear_participants(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "clear_participants: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(survey.rewarded_count.is_zero(), Error::<T>::CannotClearAfterRewards);
+
+            // Read no more than `MaxClear` participants, so that the call stays bounded
+            let participants = Self::drain_participants(survey_id, T::MaxClear::get() as usize);
+            for participant_id in participants.iter() {
+                JoinedSurveys::<T>::remove(participant_id, survey_id);
+                ParticipantScores::<T>::remove(survey_id, participant_id);
+                ParticipantResponses::<T>::remove(survey_id, participant_id);
+                RegistrationIndex::<T>::remove(survey_id, participant_id);
+            }
+
+            let removed = participants.len() as u32;
+            let number_participants = survey.number_participants.saturating_sub(removed.into());
+            // Registration starts over once no participant holds an index anymore
+            if number_participants.is_zero() {
+                RegistrationCount::<T>::remove(survey_id);
+            }
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    number_participants,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::ParticipantsCleared {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                removed,
+            });
+
+            Ok(())
+        }
+
+        /// Archive a settled survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// The survey and its participants are removed from storage, only an `ArchivedSurvey`
+        /// summary being kept. The creation deposit of a completed survey is returned to the owner.
+        /// Archived surveys cannot be operated anymore.
+        ///
+        /// The first call archives the survey. Every call then removes at most `MaxClear`
+        /// participants with their entries, then at most `MaxClear` entries left in the other
+        /// maps of the survey: the call has to be repeated until `SurveyArchived` is emitted for
+        /// surveys with more participants.
+        ///
+        /// REQUIRES: Survey has to be created already, or its archival not finished.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should be completed or cancelled, with nothing left in escrow.
+        ///
+        /// Emits `SurveyArchived` once every entry of the survey is removed
+        #[pallet::call_index(31)]
+        #[pallet::weight(Pallet::<T>::archive_survey_weight())]
+        pub fn archive_survey(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "archive_survey: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            match SurveysMap::<T>::get(survey_id) {
+                // Resume the archival of an archived survey
+                None => {
+                    ensure!(
+                        Self::is_archival_pending(survey_id),
+                        Error::<T>::SurveyNotCreated
+                    );
+                    let archived =
+                        Archive::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+                    // Check that caller is owner
+                    ensure!(archived.owner == caller, Error::<T>::NotOwnerOfSurvey);
+                },
                 Some(survey) => {
                     // Check that caller is owner
                     ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
 
-                    // Check that survey is already funded
-                    ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
-
-                    // Check that participant is already registered
+                    // Check that survey is settled
                     ensure!(
-                        Self::is_participant(survey_id, participant_id.clone()),
-                        Error::<T>::ParticipantNotRegistered
+                        matches!(survey.status, Status::Completed | Status::Cancelled)
+                            && survey.escrow_remaining.is_zero()
+                            && survey.bonus_escrow.is_zero(),
+                        Error::<T>::SurveyNotSettled
                     );
 
-                    // Check that participant has not already been rewarded
-                    ensure!(
-                        !Self::is_participant_already_rewarded(survey_id, participant_id.clone()),
-                        Error::<T>::ParticipantAlreadyRewarded
+                    Self::credit(&caller, survey.deposit, survey_id)?;
+
+                    // Removing the survey first leaves it non-operable until its entries are
+                    // all removed
+                    RegistrationCount::<T>::remove(survey_id);
+                    RewardAmounts::<T>::remove(survey_id);
+                    FinalizationCursor::<T>::remove(survey_id);
+                    PendingOwners::<T>::remove(survey_id);
+                    SurveysMap::<T>::remove(survey_id);
+                    SurveyCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+
+                    Archive::<T>::insert(
+                        survey_id,
+                        ArchivedSurvey {
+                            owner: survey.owner_id,
+                            total_participants: survey.number_participants,
+                            total_paid: survey.total_paid,
+                            completed_at: survey.completed_at,
+                        },
                     );
+                    ArchivalPending::<T>::insert(survey_id, true);
+                },
+            }
 
-                    // Reward participant
-                    let participant_balance: BalanceOf<T> =
-                        <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(
-                            &participant_id,
-                        );
+            if Self::clear_archived_entries(survey_id) {
+                ArchivalPending::<T>::remove(survey_id);
+
+                Self::emit_event(Event::SurveyArchived {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Set the reward paid to referrers
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `referral_reward`: the amount paid to the referrer of every participant
+        ///
+        /// Funding the survey sets `referral_reward * participants_limit` aside for referrals, the
+        /// rest being split between participants.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `ReferralRewardSet`
+        #[pallet::call_index(28)]
+        #[pallet::weight(u64::default())]
+        pub fn set_referral_reward(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            referral_reward: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-                    // We can unwrap here as survey is verified to have been funded already.
-                    let reward_amount = survey.reward_amount.unwrap_or_default();
+            log::debug!(
+                target: LOG_TARGET,
+                "set_referral_reward: survey_id = {:?}, caller = {:?}, referral_reward = {:?}",
+                survey_id, caller, referral_reward
+            );
 
-                    let new_participant_balance = participant_balance
-                        .checked_add(&reward_amount)
-                        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
-                        .map_err(|e| {
-                            #[cfg(test)]
-                            panic!("defensive error happened: {:?}", e);
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
 
-                            log::error!(target: "..", "defensive error happened: {:?}", e);
-                            e
-                        })?;
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
 
-                    // Update participant balance
-                    let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
-                        &participant_id,
-                        new_participant_balance,
-                    );
+            // Referral reward cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
 
-                    // Update reward storage unit
-                    ParticipantsRewarded::<T>::insert(survey_id, participant_id.clone(), true);
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    referral_reward,
+                    ..survey
+                },
+            );
 
-                    Self::deposit_event(Event::RewardClaimed {
-                        survey_id,
-                        participant_id,
-                        reward_amount,
-                    });
+            Self::emit_event(Event::ReferralRewardSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                referral_reward,
+            });
 
-                    Ok(())
+            Ok(())
+        }
+
+        /// Register a participant to a survey and reward the participant who referred them
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the account of the participant
+        /// - `referrer`: the account of an already registered participant
+        ///
+        /// The referral reward of the survey, if any, is paid to `referrer` from the survey escrow.
+        ///
+        /// REQUIRES: Same as `register_participant`.
+        /// REQUIRES: Referrer should be a registered participant other than `participant_id`.
+        ///
+        /// Emits `NewParticipantRegistered`, and `ReferralRewardPaid` if the referral reward is not
+        /// zero
+        #[pallet::call_index(29)]
+        #[pallet::weight(u64::default())]
+        pub fn register_with_referrer(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+            referrer: AccountId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "register_with_referrer: survey_id = {:?}, caller = {:?}, participant_id = {:?}, referrer = {:?}",
+                survey_id, caller, participant_id, referrer
+            );
+
+            // Check that referrer is another registered participant
+            ensure!(
+                referrer != participant_id && Self::is_participant(survey_id, referrer.clone()),
+                Error::<T>::InvalidReferrer
+            );
+
+            Self::do_register_participant(caller, survey_id, participant_id.clone())?;
+
+            // Nothing is paid, nor reported, for a survey without referral reward
+            let has_referral_reward = Self::get_survey(survey_id)
+                .map_or(false, |survey| !survey.referral_reward.is_zero());
+            if !has_referral_reward {
+                return Ok(());
+            }
+
+            let referral_reward = Self::mutate_survey(survey_id, |survey| {
+                survey.escrow_remaining =
+                    arithmetic::sub_escrow(survey.escrow_remaining, survey.referral_reward)?;
+                Self::release_total_escrow(survey.referral_reward);
+                survey.total_paid = survey.total_paid.saturating_add(survey.referral_reward);
+                Ok(survey.referral_reward)
+            })
+            .map_err(|e| Self::defensive_error(e, survey_id, &referrer))?;
+            Self::credit(&referrer, referral_reward, survey_id)?;
+
+            Self::emit_event(Event::ReferralRewardPaid {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                referrer,
+                participant_id,
+                referral_reward,
+            });
+
+            Ok(())
+        }
+
+        /// Set the bonus asset paid to every participant on top of the native reward
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `bonus`: the asset and amount paid to every participant, `None` for no bonus
+        ///
+        /// Funding the survey takes `amount * participants_limit` of the asset from the owner.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyBonusSet`
+        #[pallet::call_index(27)]
+        #[pallet::weight(u64::default())]
+        pub fn set_survey_bonus(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            bonus: Option<(AssetIdOf<T>, AssetBalanceOf<T>)>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_survey_bonus: survey_id = {:?}, caller = {:?}, bonus = {:?}",
+                survey_id, caller, bonus
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Bonus cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            let bonus = bonus.filter(|(_, bonus_amount)| !bonus_amount.is_zero());
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    bonus: bonus.clone(),
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyBonusSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                bonus,
+            });
+
+            Ok(())
+        }
+
+        /// Register the address of a participant who completed the survey, if not already registered
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the address of the participant
+        ///
+        /// Same as `register_participant`, except that registering an already registered
+        /// participant succeeds without doing anything, which makes retries safe.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        ///
+        /// Emits `NewParticipantRegistered` if the participant was not registered yet
+        #[pallet::call_index(25)]
+        #[pallet::weight(u64::default())]
+        pub fn register_participant_idempotent(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "register_participant_idempotent: survey_id = {:?}, caller = {:?}, participant_id = {:?}",
+                survey_id, caller, participant_id
+            );
+
+            match Self::do_register_participant(caller, survey_id, participant_id.clone()) {
+                Err(e) if e == Error::<T>::ParticipantAlreadyRegistered.into() => Ok(()),
+                // Closed and full surveys are rejected before registered participants are looked up
+                Err(e)
+                    if [
+                        Error::<T>::SurveyPaused,
+                        Error::<T>::SurveyCompleted,
+                        Error::<T>::SurveyCancelled,
+                        Error::<T>::SurveyExpired,
+                        Error::<T>::MaxNumberOfParticipantsReached,
+                    ]
+                    .into_iter()
+                    .any(|error| e == error.into())
+                        && Self::is_participant(survey_id, participant_id) =>
+                    Ok(()),
+                result => result,
+            }
+        }
+
+        /// Claim reward on behalf of participant and update its balance
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the address of the participant
+        ///
+        /// Rewarding is allowed while the pallet or the survey is paused, and once the survey is
+        /// completed, so that participants who already completed the survey are not stranded.
+        /// It is not allowed once the survey is cancelled, its escrow being refunded.
+        ///
+        /// The participant never signs anything to be registered or rewarded, so it can be any
+        /// account, such as a multisig or a pure proxy.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: `RewardDelay` blocks should have passed since funding.
+        /// REQUIRES: Participant should already be registered.
+        /// REQUIRES: Reward should not have already been claimed.
+        ///
+        /// Emits `RewardClaimed`, or `RewardFailedDefensive` leaving the participant unrewarded
+        /// if escrow cannot cover the reward
+        #[pallet::call_index(4)]
+        #[pallet::weight(u64::default())]
+        pub fn reward_participant(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "reward_participant: survey_id = {:?}, caller = {:?}, participant_id = {:?}",
+                survey_id, caller, participant_id
+            );
+
+            let survey_option = SurveysMap::<T>::get(survey_id);
+
+            // Check that survey is created
+            match survey_option {
+                None => Err(Error::<T>::SurveyNotCreated.into()),
+                Some(survey) => {
+                    let reward_amount =
+                        Self::ensure_rewardable(&survey, &caller, &participant_id)?;
+
+                    Self::do_pay_reward(&survey, participant_id, reward_amount)
                 }
             }
         }
@@ -506,18 +2117,62 @@ pub mod pallet {
         /// - `status`: the address of the participant
         ///
         /// REQUIRES: Survey has to be created already.
-        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: Survey should not be cancelled.
+        /// REQUIRES: Status should not be `Cancelled`, which only `cancel_survey` sets as it
+        /// settles the escrow.
+        ///
+        /// Requirements are checked in the order above, so that a missing survey is always
+        /// reported as `SurveyNotCreated`, even while the pallet is paused.
+        ///
+        /// Completing a survey closes its registration and computes the reward of a post-hoc
+        /// survey from its actual participants.
         ///
         /// Emits `SurveyStatusUpdated`
         #[pallet::call_index(5)]
         #[pallet::weight(u64::default())]
         pub fn set_survey_status(
             origin: OriginFor<T>,
-            survey_id: SurveyId,
+            survey_id: T::SurveyId,
             new_status: Status,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
+            log::debug!(
+                target: LOG_TARGET,
+                "set_survey_status: survey_id = {:?}, caller = {:?}, new_status = {:?}",
+                survey_id, caller, new_status
+            );
+
+            Self::do_set_survey_status(caller, survey_id, new_status)
+        }
+
+        /// Set the deadline of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `deadline`: the last block at which the survey is considered running
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Deadline should be in the future.
+        ///
+        /// Emits `SurveyDeadlineSet`
+        #[pallet::call_index(6)]
+        #[pallet::weight(u64::default())]
+        pub fn set_survey_deadline(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            deadline: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_survey_deadline: survey_id = {:?}, caller = {:?}, deadline = {:?}",
+                survey_id, caller, deadline
+            );
+
             let survey_option = SurveysMap::<T>::get(survey_id);
 
             // Check that survey is created
@@ -527,25 +2182,3879 @@ pub mod pallet {
                     // Check that caller is owner
                     ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
 
-                    // Set new status
+                    // Check that deadline is in the future
+                    ensure!(
+                        deadline > frame_system::Pallet::<T>::block_number(),
+                        Error::<T>::DeadlineInThePast
+                    );
+
+                    // Set new deadline
                     let survey_updated = Survey {
-                        status: new_status.clone(),
+                        deadline: Some(deadline),
                         ..survey
                     };
 
                     SurveysMap::<T>::insert(survey_id, survey_updated);
+                    Self::schedule_expiry(survey_id, deadline);
 
-                    // Emit event
-                    Self::deposit_event(Event::SurveyStatusUpdated {
+                    Self::emit_event(Event::SurveyDeadlineSet {
                         survey_id,
-                        new_status,
+                        seq: Self::next_event_seq(survey_id),
+                        deadline,
                     });
 
                     Ok(())
                 }
             }
         }
-    }
 
-    impl<T: Config> Pallet<T> {}
+        /// Complete every survey of the list whose deadline has passed and reward the caller
+        ///
+        /// - `survey_ids`: the ids of the surveys to sweep
+        ///
+        /// Ids of surveys which are not created, not expired or already completed are ignored, as
+        /// are surveys whose completion fails. Surveys are completed as by `set_survey_status`,
+        /// post-hoc rewards being computed and yield distributed.
+        /// The caller is paid `SweepReward` from `TreasuryAccount` for every survey swept.
+        ///
+        /// Emits `SurveyStatusUpdated` for every survey swept, `ExpiredSurveysSwept`
+        #[pallet::call_index(7)]
+        #[pallet::weight(u64::default())]
+        pub fn sweep_expired(
+            origin: OriginFor<T>,
+            survey_ids: BoundedVec<T::SurveyId, T::MaxSweep>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "sweep_expired: survey_ids = {:?}, caller = {:?}",
+                survey_ids, caller
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut swept_count: u32 = 0;
+
+            for survey_id in survey_ids {
+                let Some(survey) = SurveysMap::<T>::get(survey_id) else {
+                    continue;
+                };
+
+                if matches!(survey.status, Status::Completed | Status::Cancelled)
+                    || !Self::is_expired(&survey, now)
+                {
+                    continue;
+                }
+
+                // Complete the expired survey, skipping it should its completion fail
+                let mut survey = survey;
+                let completed = frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                    Self::do_finalize(&mut survey, Some(&caller))?;
+                    SurveysMap::<T>::insert(survey_id, survey);
+                    Ok(())
+                });
+                if completed.is_err() {
+                    continue;
+                }
+                T::OnSurveyLifecycle::on_completed(survey_id);
+
+                Self::emit_event(Event::SurveyStatusUpdated {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                    new_status: Status::Completed,
+                    status_code: Status::Completed.code(),
+                    changed_by: caller.clone(),
+                });
+
+                swept_count = swept_count.saturating_add(1);
+            }
+
+            // Reward the caller for the sweeping
+            let reward_amount = T::SweepReward::get().saturating_mul(swept_count.into());
+            if !reward_amount.is_zero() {
+                <T::NativeBalance as fungible::Mutate<AccountId<T>>>::transfer(
+                    &T::TreasuryAccount::get(),
+                    &caller,
+                    reward_amount,
+                    Preservation::Preserve,
+                )?;
+            }
+
+            Self::emit_event(Event::ExpiredSurveysSwept {
+                sweeper: caller,
+                swept_count,
+                reward_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Propose a new owner for a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `proposed_owner_id`: the address of the proposed owner
+        ///
+        /// The ownership is only transferred once the proposed owner calls `accept_ownership`.
+        /// A new proposal replaces the previous one.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        ///
+        /// Emits `NewOwnerProposed`
+        #[pallet::call_index(8)]
+        #[pallet::weight(u64::default())]
+        pub fn propose_new_owner(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            proposed_owner_id: OwnerId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "propose_new_owner: survey_id = {:?}, caller = {:?}, proposed_owner_id = {:?}",
+                survey_id, caller, proposed_owner_id
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            PendingOwners::<T>::insert(survey_id, proposed_owner_id.clone());
+
+            Self::emit_event(Event::NewOwnerProposed {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                owner_id: caller,
+                proposed_owner_id,
+            });
+
+            Ok(())
+        }
+
+        /// Accept the ownership of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by the proposed owner of the survey.
+        ///
+        /// Emits `SurveyOwnershipTransferred`
+        #[pallet::call_index(9)]
+        #[pallet::weight(u64::default())]
+        pub fn accept_ownership(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "accept_ownership: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is the proposed owner
+            ensure!(
+                Self::pending_owner(survey_id) == Some(caller.clone()),
+                Error::<T>::NotProposedOwner
+            );
+
+            PendingOwners::<T>::remove(survey_id);
+
+            let old_owner_id = survey.owner_id.clone();
+            let survey_updated = Survey {
+                owner_id: caller.clone(),
+                ..survey
+            };
+            SurveysMap::<T>::insert(survey_id, survey_updated);
+
+            Self::emit_event(Event::SurveyOwnershipTransferred {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                old_owner_id,
+                new_owner_id: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Create a new survey template
+        ///
+        /// - `template`: the parameters surveys instantiated from this template will be created with
+        ///
+        /// Emits `TemplateCreated`
+        #[pallet::call_index(10)]
+        #[pallet::weight(u64::default())]
+        pub fn create_template(origin: OriginFor<T>, template: SurveyTemplate<T>) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "create_template: caller = {:?}, template = {:?}",
+                owner_id, template
+            );
+
+            let template_id = NextTemplateId::<T>::get();
+            let next_template_id = template_id
+                .checked_add(1)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+
+            Templates::<T>::insert(&owner_id, template_id, template);
+            NextTemplateId::<T>::put(next_template_id);
+
+            Self::emit_event(Event::TemplateCreated {
+                owner_id,
+                template_id,
+            });
+
+            Ok(())
+        }
+
+        /// Create a new survey from a template of the caller
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `template_id`: the id of the template to instantiate
+        ///
+        /// REQUIRES: Survey must not have been crated already
+        /// REQUIRES: Template has to be owned by the caller.
+        ///
+        /// Emits `SurveyCreated`
+        #[pallet::call_index(11)]
+        #[pallet::weight(u64::default())]
+        pub fn create_from_template(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            template_id: TemplateId,
+        ) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "create_from_template: survey_id = {:?}, caller = {:?}, template_id = {}",
+                survey_id, owner_id, template_id
+            );
+
+            let template =
+                Templates::<T>::get(&owner_id, template_id).ok_or(Error::<T>::TemplateNotFound)?;
+
+            let deadline = template
+                .duration
+                .map(|duration| frame_system::Pallet::<T>::block_number().saturating_add(duration));
+
+            Self::do_create_survey(owner_id, survey_id, template.participants_limit, deadline)
+        }
+
+        /// Delete a survey template of the caller
+        ///
+        /// - `template_id`: the id of the template to delete
+        ///
+        /// REQUIRES: Template has to be owned by the caller.
+        ///
+        /// Emits `TemplateDeleted`
+        #[pallet::call_index(12)]
+        #[pallet::weight(u64::default())]
+        pub fn delete_template(origin: OriginFor<T>, template_id: TemplateId) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "delete_template: caller = {:?}, template_id = {}",
+                owner_id, template_id
+            );
+
+            ensure!(
+                Templates::<T>::contains_key(&owner_id, template_id),
+                Error::<T>::TemplateNotFound
+            );
+
+            Templates::<T>::remove(&owner_id, template_id);
+
+            Self::emit_event(Event::TemplateDeleted {
+                owner_id,
+                template_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set the early bird bonus of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `early_bird_count`: the number of first registered participants earning the bonus
+        /// - `early_bird_multiplier`: the multiplier applied to the reward of early birds
+        ///
+        /// The funding of the survey has to cover the bonus of every early bird on top of the
+        /// reward of every participant.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        /// REQUIRES: Multiplier should not be zero.
+        ///
+        /// Emits `SurveyEarlyBirdSet`
+        #[pallet::call_index(15)]
+        #[pallet::weight(u64::default())]
+        pub fn set_early_bird(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            early_bird_count: BalanceOf<T>,
+            early_bird_multiplier: u32,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_early_bird: survey_id = {:?}, caller = {:?}, early_bird_count = {:?}, early_bird_multiplier = {}",
+                survey_id, caller, early_bird_count, early_bird_multiplier
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Reward amount is computed at funding time
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            ensure!(early_bird_multiplier > 0, Error::<T>::InvalidEarlyBirdMultiplier);
+
+            let survey_updated = Survey {
+                early_bird_count,
+                early_bird_multiplier,
+                ..survey
+            };
+            SurveysMap::<T>::insert(survey_id, survey_updated);
+
+            Self::emit_event(Event::SurveyEarlyBirdSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                early_bird_count,
+                early_bird_multiplier,
+            });
+
+            Ok(())
+        }
+
+        /// Reduce the participants limit of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `new_limit`: the new max number of participants for this survey
+        ///
+        /// The reward amount is kept unchanged, the rewards of the freed slots are added to the
+        /// surplus the owner can reclaim.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: New limit should be lower than the current one.
+        /// REQUIRES: New limit should not be lower than the number of registered participants.
+        ///
+        /// Emits `ParticipantsLimitReduced`
+        #[pallet::call_index(16)]
+        #[pallet::weight(u64::default())]
+        pub fn reduce_participants_limit(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            new_limit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "reduce_participants_limit: survey_id = {:?}, caller = {:?}, new_limit = {:?}",
+                survey_id, caller, new_limit
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(new_limit < survey.participants_limit, Error::<T>::LimitNotReduced);
+            ensure!(
+                new_limit >= survey.number_participants,
+                Error::<T>::LimitBelowCurrentParticipants
+            );
+
+            let reduced_survey = Survey {
+                participants_limit: new_limit,
+                ..survey.clone()
+            };
+
+            // Rewards of the freed slots become surplus
+            let freed_slots = Self::reward_slots(&survey)?
+                .saturating_sub(Self::reward_slots(&reduced_survey)?);
+            let freed_amount =
+                arithmetic::mul_reward(survey.reward_amount.unwrap_or_default(), freed_slots)?;
+            let reclaimable_surplus = reduced_survey
+                .reclaimable_surplus
+                .checked_add(&freed_amount)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    reclaimable_surplus,
+                    ..reduced_survey
+                },
+            );
+
+            Self::emit_event(Event::ParticipantsLimitReduced {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                new_limit,
+                reclaimable_surplus,
+            });
+
+            Ok(())
+        }
+
+        /// Extend the participants limit of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `new_limit`: the new max number of participants for this survey
+        ///
+        /// If the survey is funded with a known reward, either:
+        /// - the rewards are re-divided between the new slots, which lowers the reward, if
+        ///   `recompute_reward_on_extend` is set,
+        /// - or the owner funds the new slots, rewards and referral and bonus rewards included.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should be active.
+        /// REQUIRES: New limit should be higher than the current one.
+        /// REQUIRES: No participant should be rewarded yet if the reward is re-divided.
+        /// REQUIRES: Owner should have enough balance per `FundingSource` if the new slots are
+        /// funded.
+        ///
+        /// Emits `ParticipantsLimitExtended`
+        #[pallet::call_index(44)]
+        #[pallet::weight(u64::default())]
+        pub fn extend_participants_limit(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            new_limit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "extend_participants_limit: survey_id = {:?}, caller = {:?}, new_limit = {:?}",
+                survey_id, caller, new_limit
+            );
+
+            Self::ensure_not_paused()?;
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            Self::ensure_active(&survey)?;
+
+            ensure!(new_limit > survey.participants_limit, Error::<T>::LimitNotExtended);
+
+            let extended_survey = Survey {
+                participants_limit: new_limit,
+                ..survey.clone()
+            };
+
+            // Rewards are only known once a survey is funded, and post-hoc rewards once finalized
+            let extended_survey = match (survey.is_funded, survey.reward_amount) {
+                (true, Some(reward_amount)) if survey.recompute_reward_on_extend =>
+                    Self::redivide_rewards(&survey, extended_survey, reward_amount, 0u32.into())?,
+                (true, Some(reward_amount)) =>
+                    Self::fund_extension(&survey, extended_survey, reward_amount)?,
+                _ => extended_survey,
+            };
+            let reward_amount = extended_survey.reward_amount;
+
+            SurveysMap::<T>::insert(survey_id, extended_survey);
+            if let Some(reward_amount) = reward_amount {
+                RewardAmounts::<T>::insert(survey_id, reward_amount);
+            }
+
+            Self::emit_event(Event::ParticipantsLimitExtended {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                new_limit,
+                reward_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Reclaim the surplus of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// The surplus is paid back to the funder of the survey.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should have a surplus.
+        ///
+        /// Emits `SurplusReclaimed`
+        #[pallet::call_index(17)]
+        #[pallet::weight(u64::default())]
+        pub fn reclaim_surplus(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "reclaim_surplus: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            let amount = survey.reclaimable_surplus;
+            ensure!(!amount.is_zero(), Error::<T>::NoSurplusToReclaim);
+
+            // Update funder balance
+            let funder_id = survey.funder_id.clone().unwrap_or_else(|| caller.clone());
+            let funder_balance: BalanceOf<T> =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(&funder_id);
+            let new_funder_balance = funder_balance
+                .checked_add(&amount)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+                .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+            let escrow_remaining = arithmetic::sub_escrow(survey.escrow_remaining, amount)
+                .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+            let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                &funder_id,
+                new_funder_balance,
+            );
+            Self::release_total_escrow(amount);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    reclaimable_surplus: 0u32.into(),
+                    escrow_remaining,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurplusReclaimed {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                owner_id: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Change the reward paid to every participant of a funded survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `reward_amount`: the new reward of a regular participant
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should be funded, without post-hoc rewards.
+        /// REQUIRES: No participant should have been rewarded.
+        /// REQUIRES: Funding should cover the new reward for every participant.
+        /// REQUIRES: Reward should be within `MaxRewardPerParticipant`.
+        ///
+        /// Emits `RewardAmountSet`
+        #[pallet::call_index(35)]
+        #[pallet::weight(u64::default())]
+        pub fn set_reward_amount(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            reward_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_reward_amount: survey_id = {:?}, caller = {:?}, reward_amount = {:?}",
+                survey_id, caller, reward_amount
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
+            ensure!(!survey.post_hoc_rewards, Error::<T>::RewardsDeferred);
+            ensure!(survey.rewarded_count.is_zero(), Error::<T>::RewardAlreadyPaid);
+
+            // Check that funding covers the new reward
+            let rewards_fund = survey
+                .funded_amount
+                .unwrap_or_default()
+                .saturating_sub(Self::referral_budget(&survey)?);
+            Self::ensure_rewards_covered(reward_amount, Self::reward_slots(&survey)?, rewards_fund)?;
+            Self::ensure_within_reward_cap(&survey, reward_amount)?;
+            ensure!(reward_amount >= T::MinReward::get(), Error::<T>::RewardBelowMinimum);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    reward_amount: Some(reward_amount),
+                    reward_remainder: 0u32.into(),
+                    ..survey
+                },
+            );
+            RewardAmounts::<T>::insert(survey_id, reward_amount);
+
+            Self::emit_event(Event::RewardAmountSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                reward_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Top up the escrow of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `amount`: the amount moved from the owner to the survey escrow
+        ///
+        /// Recovers surveys whose escrow falls short of the rewards owed to their participants.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should be funded and not cancelled.
+        /// REQUIRES: Owner should have enough balance per `FundingSource`.
+        ///
+        /// Emits `EscrowToppedUp`
+        #[pallet::call_index(33)]
+        #[pallet::weight(u64::default())]
+        pub fn top_up_escrow(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "top_up_escrow: survey_id = {:?}, caller = {:?}, amount = {:?}",
+                survey_id, caller, amount
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
+            ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+            // Update owner balance
+            let new_owner_balance =
+                Self::debited_balance(&caller, amount, Error::<T>::NotEnoughBalanceForFunding)?;
+            let escrow_remaining = survey
+                .escrow_remaining
+                .checked_add(&amount)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+                .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+            Self::reserve_total_escrow(amount)?;
+            let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                &caller,
+                new_owner_balance,
+            );
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    escrow_remaining,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::EscrowToppedUp {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                amount,
+                escrow_remaining,
+            });
+
+            Ok(())
+        }
+
+        /// Set the visibility of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `is_public`: whether the survey is returned by discovery queries
+        ///
+        /// Surveys are public when created.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        ///
+        /// Emits `SurveyVisibilitySet`
+        #[pallet::call_index(18)]
+        #[pallet::weight(u64::default())]
+        pub fn set_survey_visibility(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            is_public: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_survey_visibility: survey_id = {:?}, caller = {:?}, is_public = {}",
+                survey_id, caller, is_public
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            SurveysMap::<T>::insert(survey_id, Survey { is_public, ..survey });
+
+            Self::emit_event(Event::SurveyVisibilitySet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                is_public,
+            });
+
+            Ok(())
+        }
+
+        /// Add a manager to a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `manager_id`: the address of the manager
+        ///
+        /// Managers can register and reward participants and update the status of the survey.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        ///
+        /// Emits `ManagerAdded`
+        #[pallet::call_index(19)]
+        #[pallet::weight(u64::default())]
+        pub fn add_manager(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            manager_id: AccountId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "add_manager: survey_id = {:?}, caller = {:?}, manager_id = {:?}",
+                survey_id, caller, manager_id
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            Managers::<T>::insert(survey_id, &manager_id, ());
+
+            Self::emit_event(Event::ManagerAdded {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                manager_id,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a manager from a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `manager_id`: the address of the manager
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Account should be a manager of the survey.
+        ///
+        /// Emits `ManagerRemoved`
+        #[pallet::call_index(20)]
+        #[pallet::weight(u64::default())]
+        pub fn remove_manager(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            manager_id: AccountId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "remove_manager: survey_id = {:?}, caller = {:?}, manager_id = {:?}",
+                survey_id, caller, manager_id
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(
+                Self::is_manager(survey_id, &manager_id),
+                Error::<T>::ManagerNotFound
+            );
+
+            Managers::<T>::remove(survey_id, &manager_id);
+
+            Self::emit_event(Event::ManagerRemoved {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                manager_id,
+            });
+
+            Ok(())
+        }
+
+        /// Create several surveys at once
+        ///
+        /// - `surveys`: the `(survey_id, participants_limit)` pairs of the surveys to create
+        ///
+        /// The batch is atomic: if any survey cannot be created, none of them is.
+        ///
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: None of the surveys must have been crated already
+        ///
+        /// Emits `SurveyCreated` for every survey
+        #[pallet::call_index(21)]
+        #[pallet::weight(u64::default())]
+        pub fn batch_create_surveys(
+            origin: OriginFor<T>,
+            surveys: BoundedVec<(T::SurveyId, BalanceOf<T>), T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "batch_create_surveys: caller = {:?}, surveys = {:?}",
+                owner_id, surveys
+            );
+
+            for (survey_id, participants_limit) in surveys {
+                Self::do_create_survey(owner_id.clone(), survey_id, participants_limit, None)?;
+            }
+
+            Ok(())
+        }
+
+        /// Set the status of several surveys of the caller at once
+        ///
+        /// - `updates`: the `(survey_id, status)` pairs of the status changes to apply
+        ///
+        /// The batch is atomic: if any status cannot be set, none of them is.
+        ///
+        /// REQUIRES: Same as `set_survey_status`, for every survey.
+        /// REQUIRES: Every survey should be owned by the caller.
+        ///
+        /// Emits `SurveyStatusUpdated` for every survey
+        #[pallet::call_index(49)]
+        #[pallet::weight(u64::default())]
+        pub fn batch_set_status(
+            origin: OriginFor<T>,
+            updates: BoundedVec<(T::SurveyId, Status), T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "batch_set_status: caller = {:?}, updates = {:?}",
+                caller, updates
+            );
+
+            for (survey_id, new_status) in updates {
+                let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+                // Check that caller is owner
+                ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+                Self::do_set_survey_status(caller.clone(), survey_id, new_status)?;
+            }
+
+            Ok(())
+        }
+
+        /// Move surplus escrow from a survey to another survey of the caller
+        ///
+        /// - `from_survey`: the id of the survey the surplus is taken from
+        /// - `to_survey`: the id of the survey whose escrow receives the surplus
+        /// - `amount`: the amount moved
+        ///
+        /// The surplus is the escrow not owed to registered participants, as `escrow_surplus`.
+        /// The amount is added to the funding of the destination. The reward of a destination
+        /// still open to registrations, none of whose participants is rewarded yet, is recomputed
+        /// to share it, as are rewards computed post-hoc.
+        ///
+        /// REQUIRES: Both surveys have to be created already.
+        /// REQUIRES: Both surveys should be owned by the caller.
+        /// REQUIRES: Source should be completed or cancelled.
+        /// REQUIRES: Destination should be funded and not cancelled.
+        /// REQUIRES: Amount should not exceed the surplus of the source.
+        /// REQUIRES: Recomputed reward should be within `MaxRewardPerParticipant`.
+        ///
+        /// Emits `SurplusTransferred`
+        #[pallet::call_index(50)]
+        #[pallet::weight(u64::default())]
+        pub fn transfer_surplus(
+            origin: OriginFor<T>,
+            from_survey: T::SurveyId,
+            to_survey: T::SurveyId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "transfer_surplus: from_survey = {:?}, to_survey = {:?}, caller = {:?}, amount = {:?}",
+                from_survey, to_survey, caller, amount
+            );
+
+            let source = SurveysMap::<T>::get(from_survey).ok_or(Error::<T>::SurveyNotCreated)?;
+            let destination =
+                SurveysMap::<T>::get(to_survey).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner of both surveys
+            ensure!(source.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+            ensure!(destination.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(from_survey != to_survey, Error::<T>::CannotTransferToSameSurvey);
+            ensure!(
+                matches!(source.status, Status::Completed | Status::Cancelled),
+                Error::<T>::SurveyNotEnded
+            );
+            ensure!(destination.is_funded, Error::<T>::SurveyNotFunded);
+            ensure!(destination.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+            let surplus = Self::escrow_surplus(from_survey).unwrap_or_default();
+            ensure!(amount <= surplus, Error::<T>::AmountExceedsSurplus);
+
+            let escrow_remaining = arithmetic::sub_escrow(source.escrow_remaining, amount)
+                .map_err(|e| Self::defensive_error(e, from_survey, &caller))?;
+            let destination_escrow = destination
+                .escrow_remaining
+                .checked_add(&amount)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+                .map_err(|e| Self::defensive_error(e, to_survey, &caller))?;
+            let destination_funding = destination
+                .funded_amount
+                .unwrap_or_default()
+                .checked_add(&amount)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+                .map_err(|e| Self::defensive_error(e, to_survey, &caller))?;
+
+            SurveysMap::<T>::insert(
+                from_survey,
+                Survey {
+                    escrow_remaining,
+                    reclaimable_surplus: source.reclaimable_surplus.saturating_sub(amount),
+                    ..source
+                },
+            );
+            let funded_destination = Survey {
+                escrow_remaining: destination_escrow,
+                funded_amount: Some(destination_funding),
+                ..destination.clone()
+            };
+
+            // Share the amount between the slots of a destination still open to registrations
+            let funded_destination = match destination.reward_amount {
+                Some(reward_amount)
+                    if !destination.post_hoc_rewards
+                        && destination.rewarded_count.is_zero()
+                        && matches!(destination.status, Status::Active | Status::Paused) =>
+                {
+                    let funded_destination = Self::redivide_rewards(
+                        &destination,
+                        funded_destination,
+                        reward_amount,
+                        amount,
+                    )?;
+                    Self::ensure_within_reward_cap(
+                        &funded_destination,
+                        funded_destination.reward_amount.unwrap_or_default(),
+                    )?;
+                    funded_destination
+                },
+                _ => funded_destination,
+            };
+            if let Some(reward_amount) = funded_destination.reward_amount {
+                RewardAmounts::<T>::insert(to_survey, reward_amount);
+            }
+            SurveysMap::<T>::insert(to_survey, funded_destination);
+
+            Self::emit_event(Event::SurplusTransferred {
+                survey_id: from_survey,
+                seq: Self::next_event_seq(from_survey),
+                to_survey,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Update the hash of the response of the caller to a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `new_hash`: the hash of the new response
+        ///
+        /// Registration does not record a hash, so the first call sets it.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Survey should be active.
+        /// REQUIRES: Caller should be registered to the survey.
+        /// REQUIRES: Caller should not have been rewarded.
+        ///
+        /// Emits `ResponseHashUpdated`
+        #[pallet::call_index(51)]
+        #[pallet::weight(u64::default())]
+        pub fn update_response_hash(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            new_hash: T::Hash,
+        ) -> DispatchResult {
+            let participant_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "update_response_hash: survey_id = {:?}, participant_id = {:?}, new_hash = {:?}",
+                survey_id, participant_id, new_hash
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            Self::ensure_active(&survey)?;
+            ensure!(
+                Self::is_participant(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantNotRegistered
+            );
+            ensure!(
+                !Self::is_participant_already_rewarded(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantAlreadyRewarded
+            );
+
+            ParticipantResponses::<T>::insert(survey_id, &participant_id, new_hash);
+
+            Self::emit_event(Event::ResponseHashUpdated {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+                response_hash: new_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Reward several participants of a survey at once, emitting a single summary event
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participants`: the accounts of the participants
+        ///
+        /// Storage is updated for every participant as by `reward_participant`, but `RewardClaimed`
+        /// is not emitted so that large batches do not bloat blocks. The batch is atomic.
+        ///
+        /// REQUIRES: Same as `reward_participant`, for every participant.
+        ///
+        /// Emits `BatchRewarded`
+        #[pallet::call_index(36)]
+        #[pallet::weight(u64::default())]
+        pub fn batch_reward_silent(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participants: BoundedVec<ParticipantId<T>, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "batch_reward_silent: survey_id = {:?}, caller = {:?}, participants = {:?}",
+                survey_id, caller, participants
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            let mut count: u32 = 0;
+            let mut total: BalanceOf<T> = 0u32.into();
+            for participant_id in participants {
+                let reward_amount = Self::ensure_rewardable(&survey, &caller, &participant_id)?;
+                if let Some(paid_amount) =
+                    Self::do_pay_reward_silently(&survey, participant_id, reward_amount)?
+                {
+                    count = count.saturating_add(1);
+                    total = total.saturating_add(paid_amount);
+                }
+            }
+
+            Self::emit_event(Event::BatchRewarded {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                count,
+                total,
+            });
+
+            Ok(())
+        }
+
+        /// Create a new survey with an id generated on-chain
+        ///
+        /// - `participants_limit`: The max number of participants for this survey
+        ///
+        /// The survey gets the lowest id above every survey created so far, skipping any id
+        /// already in use.
+        ///
+        /// REQUIRES: Pallet should not be paused.
+        ///
+        /// Emits `SurveyCreated`
+        #[pallet::call_index(32)]
+        #[pallet::weight(u64::default())]
+        pub fn create_survey_auto_id(
+            origin: OriginFor<T>,
+            participants_limit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let owner_id = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "create_survey_auto_id: caller = {:?}, participants_limit = {:?}",
+                owner_id, participants_limit
+            );
+
+            let mut survey_id = NextSurveyId::<T>::get();
+            while SurveysMap::<T>::contains_key(survey_id) || Archive::<T>::contains_key(survey_id) {
+                survey_id = survey_id
+                    .checked_add(&One::one())
+                    .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+            }
+
+            Self::do_create_survey(owner_id, survey_id, participants_limit, None)
+        }
+
+        /// Register several participants to a survey at once
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participants`: the accounts of the participants
+        ///
+        /// The batch is atomic: if any participant cannot be registered, none of them is.
+        ///
+        /// REQUIRES: Same as `register_participant`, for every participant.
+        /// REQUIRES: No participant should appear twice in the batch.
+        ///
+        /// Emits `NewParticipantRegistered` for every participant
+        #[pallet::call_index(30)]
+        #[pallet::weight(u64::default())]
+        pub fn batch_register_participants(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participants: BoundedVec<ParticipantId<T>, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "batch_register_participants: survey_id = {:?}, caller = {:?}, participants = {:?}",
+                survey_id, caller, participants
+            );
+
+            // Check that no participant appears twice, which would otherwise fail halfway
+            let mut seen = BTreeSet::new();
+            ensure!(
+                participants.iter().all(|participant_id| seen.insert(participant_id)),
+                Error::<T>::DuplicateInBatch
+            );
+
+            for participant_id in participants {
+                Self::do_register_participant(caller.clone(), survey_id, participant_id)?;
+            }
+
+            Ok(())
+        }
+
+        /// Set whether the rewards of a survey are computed post-hoc
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `post_hoc_rewards`: whether rewards are computed by `finalize_rewards`
+        ///
+        /// Post-hoc surveys do not reward participants one by one: their funding is split equally
+        /// between the actual participants by `finalize_rewards`. Early bird bonuses do not apply.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyRewardModeSet`
+        #[pallet::call_index(22)]
+        #[pallet::weight(u64::default())]
+        pub fn set_post_hoc_rewards(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            post_hoc_rewards: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_post_hoc_rewards: survey_id = {:?}, caller = {:?}, post_hoc_rewards = {}",
+                survey_id, caller, post_hoc_rewards
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Reward mode cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    post_hoc_rewards,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyRewardModeSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                post_hoc_rewards,
+            });
+
+            Ok(())
+        }
+
+        /// Split the funding of a post-hoc survey between its participants and reward them all
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// Each participant receives `funded_amount / number_participants`. The survey is then
+        /// completed, so that no participant can register after the reward has been fixed.
+        ///
+        /// At most `MaxBatchSize` participants are paid per call: the call has to be repeated,
+        /// resuming after the last participant paid, until `finalization_cursor` is cleared for
+        /// surveys with more participants.
+        ///
+        /// Like `reward_participant`, this is allowed while the pallet is paused.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: Survey should be funded with post-hoc rewards.
+        /// REQUIRES: Rewards should not be already finalized, unless participants are left to pay.
+        /// REQUIRES: Survey should have at least one participant.
+        /// REQUIRES: `RewardDelay` blocks should have passed since funding.
+        ///
+        /// Emits `RewardClaimed` for every participant paid, `SurveyStatusUpdated` on the first
+        /// call, and `RewardsFinalized` with the number of participants paid by the call
+        #[pallet::call_index(23)]
+        #[pallet::weight(Pallet::<T>::finalize_rewards_weight())]
+        pub fn finalize_rewards(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "finalize_rewards: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            let mut survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner or manager
+            Self::ensure_owner_or_manager(&survey, &caller)?;
+
+            // Check that survey is already funded
+            ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
+
+            ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+            ensure!(survey.post_hoc_rewards, Error::<T>::NotPostHocSurvey);
+
+            let cursor = FinalizationCursor::<T>::get(survey_id);
+            let is_first_call = survey.reward_amount.is_none();
+            if is_first_call {
+                ensure!(
+                    !survey.number_participants.is_zero(),
+                    Error::<T>::NoParticipantsToReward
+                );
+                Self::ensure_reward_delay_passed(&survey)?;
+
+                Self::do_finalize(&mut survey, Some(&caller))?;
+                SurveysMap::<T>::insert(survey_id, survey.clone());
+            } else {
+                // Only resume paying the participants left by an earlier call
+                ensure!(cursor.is_some(), Error::<T>::RewardsAlreadyFinalized);
+            }
+            let reward_amount = survey
+                .reward_amount
+                .ok_or(Error::<T>::DefensiveRewardAmountMissing)
+                .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+
+            // Read one participant past the batch to know whether any is left for another call
+            let max_batch = T::MaxBatchSize::get() as usize;
+            let mut participants =
+                Self::participants_after(survey_id, cursor, max_batch.saturating_add(1));
+            let has_more = participants.len() > max_batch;
+            participants.truncate(max_batch);
+            let mut rewarded_count: u32 = 0;
+            for participant_id in participants.iter() {
+                if Self::is_participant_already_rewarded(survey_id, participant_id.clone()) {
+                    continue;
+                }
+                Self::do_pay_reward(&survey, participant_id.clone(), reward_amount)?;
+                if Self::is_participant_already_rewarded(survey_id, participant_id.clone()) {
+                    rewarded_count = rewarded_count.saturating_add(1);
+                }
+            }
+
+            // Resume after the last participant paid, unless none is left
+            match participants.last() {
+                Some(last) if has_more => FinalizationCursor::<T>::insert(survey_id, last),
+                _ => FinalizationCursor::<T>::remove(survey_id),
+            }
+
+            if is_first_call {
+                T::OnSurveyLifecycle::on_completed(survey_id);
+
+                Self::emit_event(Event::SurveyStatusUpdated {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                    new_status: Status::Completed,
+                    status_code: Status::Completed.code(),
+                    changed_by: caller,
+                });
+            }
+
+            Self::emit_event(Event::RewardsFinalized {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                reward_amount,
+                rewarded_count,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        ///
+        /// The remaining escrow is refunded to the funder and the bonus escrow to the owner, as is
+        /// the creation deposit minus the
+        /// `CancellationSlash` fraction, which goes to `SlashDestination`.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already cancelled.
+        ///
+        /// Emits `SurveyCancelled`
+        #[pallet::call_index(24)]
+        #[pallet::weight(u64::default())]
+        pub fn cancel_survey(origin: OriginFor<T>, survey_id: T::SurveyId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "cancel_survey: survey_id = {:?}, caller = {:?}",
+                survey_id, caller
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+            let slashed_amount = T::CancellationSlash::get() * survey.deposit;
+            let refunded_deposit = survey.deposit.saturating_sub(slashed_amount);
+            let refunded_amount = refunded_deposit
+                .checked_add(&survey.escrow_remaining)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+                .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+
+            // Escrow goes back to whoever funded the survey
+            let funder_id = survey.funder_id.clone().unwrap_or_else(|| caller.clone());
+            Self::credit(&caller, refunded_deposit, survey_id)?;
+            Self::credit(&funder_id, survey.escrow_remaining, survey_id)?;
+            Self::release_total_escrow(survey.escrow_remaining);
+            Self::credit(&T::SlashDestination::get(), slashed_amount, survey_id)?;
+
+            // Refund the bonus escrow
+            if let Some((asset_id, _)) = survey.bonus {
+                if !survey.bonus_escrow.is_zero() {
+                    <T::BonusAssets as fungibles::Mutate<AccountId<T>>>::mint_into(
+                        asset_id,
+                        &caller,
+                        survey.bonus_escrow,
+                    )?;
+                }
+            }
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    status: Status::Cancelled,
+                    completed_at: Some(frame_system::Pallet::<T>::block_number()),
+                    status_changed_by: Some(caller.clone()),
+                    deposit: 0u32.into(),
+                    bonus_escrow: 0u32.into(),
+                    escrow_remaining: 0u32.into(),
+                    reclaimable_surplus: 0u32.into(),
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyCancelled {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                refunded_amount,
+                slashed_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Pause or unpause the pallet
+        ///
+        /// - `paused`: whether the pallet should be paused
+        ///
+        /// While paused, surveys cannot be created, funded or have their participants and status
+        /// updated. Rewards of already registered participants can still be paid, through
+        /// `reward_participant` and `finalize_rewards`, so that respondents are not stranded.
+        ///
+        /// REQUIRES: Can only be called by `AdminOrigin`.
+        ///
+        /// Emits `PalletPauseSet`
+        #[pallet::call_index(14)]
+        #[pallet::weight(u64::default())]
+        pub fn set_pallet_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            log::debug!(target: LOG_TARGET, "set_pallet_paused: paused = {}", paused);
+
+            PalletPaused::<T>::put(paused);
+
+            Self::emit_event(Event::PalletPauseSet { paused });
+
+            Ok(())
+        }
+
+        /// Set the number of blocks over which rewards of a survey are vested
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `vesting_blocks`: the vesting period, `None` or zero to pay rewards immediately
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyVestingSet`
+        #[pallet::call_index(13)]
+        #[pallet::weight(u64::default())]
+        pub fn set_vesting_blocks(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            vesting_blocks: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_vesting_blocks: survey_id = {:?}, caller = {:?}, vesting_blocks = {:?}",
+                survey_id, caller, vesting_blocks
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Vesting terms cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            let vesting_blocks = vesting_blocks.filter(|blocks| !blocks.is_zero());
+            let survey_updated = Survey {
+                vesting_blocks,
+                ..survey
+            };
+            SurveysMap::<T>::insert(survey_id, survey_updated);
+
+            Self::emit_event(Event::SurveyVestingSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                vesting_blocks,
+            });
+
+            Ok(())
+        }
+
+        /// Set the number of participants a survey needs before its rewards can be paid
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `min_participants`: the minimum number of participants, zero for no minimum
+        ///
+        /// If the survey is cancelled before reaching it, its escrow is refunded to the owner.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        /// REQUIRES: Minimum should not be above the participants limit.
+        ///
+        /// Emits `SurveyMinParticipantsSet`
+        #[pallet::call_index(38)]
+        #[pallet::weight(u64::default())]
+        pub fn set_min_participants(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            min_participants: BalanceOf<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_min_participants: survey_id = {:?}, caller = {:?}, min_participants = {:?}",
+                survey_id, caller, min_participants
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Validity threshold cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            ensure!(
+                min_participants <= survey.participants_limit,
+                Error::<T>::MinParticipantsAboveLimit
+            );
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    min_participants,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyMinParticipantsSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                min_participants,
+            });
+
+            Ok(())
+        }
+
+        /// Set whether the remainder of the funding of a survey is distributed to participants
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `distribute_remainder`: whether the remainder left by dividing the funding between
+        ///   participants is paid out, one unit to each of the first registered participants
+        ///
+        /// Does not apply to surveys with post-hoc rewards.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyRemainderDistributionSet`
+        #[pallet::call_index(39)]
+        #[pallet::weight(u64::default())]
+        pub fn set_distribute_remainder(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            distribute_remainder: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_distribute_remainder: survey_id = {:?}, caller = {:?}, distribute_remainder = {}",
+                survey_id, caller, distribute_remainder
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Remainder is computed at funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    distribute_remainder,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyRemainderDistributionSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                distribute_remainder,
+            });
+
+            Ok(())
+        }
+
+        /// Set the order in which `distribute_rewards` pays the participants of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `distribution`: registration order or random order
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyDistributionOrderSet`
+        #[pallet::call_index(40)]
+        #[pallet::weight(u64::default())]
+        pub fn set_distribution_order(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            distribution: DistributionOrder,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_distribution_order: survey_id = {:?}, caller = {:?}, distribution = {:?}",
+                survey_id, caller, distribution
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Selection rules cannot change once participants can rely on the funding
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    distribution,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyDistributionOrderSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                distribution,
+            });
+
+            Ok(())
+        }
+
+        /// Set whether extending the participants limit of a survey re-divides its rewards
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `recompute_reward_on_extend`: whether `extend_participants_limit` re-divides the
+        ///   rewards between the new slots rather than requiring funding for them
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        ///
+        /// Emits `SurveyRecomputeOnExtendSet`
+        #[pallet::call_index(43)]
+        #[pallet::weight(u64::default())]
+        pub fn set_recompute_reward_on_extend(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            recompute_reward_on_extend: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_recompute_reward_on_extend: survey_id = {:?}, caller = {:?}, recompute_reward_on_extend = {}",
+                survey_id, caller, recompute_reward_on_extend
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            SurveysMap::<T>::insert(
+                survey_id,
+                Survey {
+                    recompute_reward_on_extend,
+                    ..survey
+                },
+            );
+
+            Self::emit_event(Event::SurveyRecomputeOnExtendSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                recompute_reward_on_extend,
+            });
+
+            Ok(())
+        }
+
+        /// Reward up to `max_count` participants of a survey, in its distribution order
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `max_count`: the maximum number of participants to pay, capped to `MaxBatchSize`
+        ///
+        /// Participants not rewarded yet are paid in the order returned by `reward_queue`, until
+        /// `max_count` is reached or escrow no longer covers the next reward.
+        ///
+        /// REQUIRES: Same as `reward_participant`, for every participant paid.
+        ///
+        /// Emits `BatchRewarded`
+        #[pallet::call_index(41)]
+        #[pallet::weight(u64::default())]
+        pub fn distribute_rewards(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            max_count: u32,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "distribute_rewards: survey_id = {:?}, caller = {:?}, max_count = {}",
+                survey_id, caller, max_count
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            let max_count = max_count.min(T::MaxBatchSize::get());
+            let mut count: u32 = 0;
+            let mut total: BalanceOf<T> = 0u32.into();
+            for participant_id in Self::reward_queue(survey_id).into_iter().take(max_count as usize) {
+                let reward_amount = Self::ensure_rewardable(&survey, &caller, &participant_id)?;
+
+                // Stop once escrow no longer covers the next reward
+                let escrow_remaining = Self::get_survey(survey_id)
+                    .map(|survey| survey.escrow_remaining)
+                    .unwrap_or_default();
+                if escrow_remaining < reward_amount {
+                    break;
+                }
+
+                if let Some(paid_amount) =
+                    Self::do_pay_reward_silently(&survey, participant_id, reward_amount)?
+                {
+                    count = count.saturating_add(1);
+                    total = total.saturating_add(paid_amount);
+                }
+            }
+
+            Self::emit_event(Event::BatchRewarded {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                count,
+                total,
+            });
+
+            Ok(())
+        }
+
+        /// Broadcast an announcement to the participants of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `message`: the announcement, which is only emitted and never stored
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        ///
+        /// Emits `SurveyAnnouncement`
+        #[pallet::call_index(45)]
+        #[pallet::weight(u64::default())]
+        pub fn announce(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            message: BoundedVec<u8, T::MaxAnnouncementLen>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "announce: survey_id = {:?}, caller = {:?}, message = {:?}",
+                survey_id, caller, message
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            Self::emit_event(Event::SurveyAnnouncement {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                message,
+            });
+
+            Ok(())
+        }
+
+        /// Claw back the reward of a fraudulent participant
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the address of the rewarded participant
+        /// - `reason_hash`: the hash of the off-chain justification
+        ///
+        /// The reward is taken back from the participant into escrow, and the participant is
+        /// unregistered so that it cannot be rewarded again. Bonus assets are not clawed back.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be cancelled, its escrow being refunded.
+        /// REQUIRES: Participant should have been rewarded at most `ClawbackWindow` blocks ago.
+        /// REQUIRES: Participant should still hold the reward, unlocked.
+        ///
+        /// Emits `RewardClawedBack`
+        #[pallet::call_index(46)]
+        #[pallet::weight(u64::default())]
+        pub fn clawback_reward(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+            reason_hash: T::Hash,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "clawback_reward: survey_id = {:?}, caller = {:?}, participant_id = {:?}, reason_hash = {:?}",
+                survey_id, caller, participant_id, reason_hash
+            );
+
+            let amount = Self::do_clawback(survey_id, &caller, &participant_id, true)?;
+
+            Self::emit_event(Event::RewardClawedBack {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+                amount,
+                reason_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Dispute the reward paid to a participant
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the address of the rewarded participant
+        ///
+        /// The reward stays with the participant until the dispute is resolved with
+        /// `resolve_dispute`, which can happen after `ClawbackWindow` has passed.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be cancelled, its escrow being refunded.
+        /// REQUIRES: Participant should have been rewarded at most `ClawbackWindow` blocks ago.
+        /// REQUIRES: Reward should not be already disputed.
+        ///
+        /// Emits `RewardDisputed`
+        #[pallet::call_index(52)]
+        #[pallet::weight(u64::default())]
+        pub fn dispute_reward(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "dispute_reward: survey_id = {:?}, caller = {:?}, participant_id = {:?}",
+                survey_id, caller, participant_id
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+            let receipt = Self::reward_receipt(survey_id, &participant_id)
+                .ok_or(Error::<T>::ParticipantNotRewarded)?;
+            ensure!(
+                Self::within_clawback_window(receipt.block),
+                Error::<T>::ClawbackWindowExpired
+            );
+            ensure!(
+                !DisputedRewards::<T>::contains_key(survey_id, &participant_id),
+                Error::<T>::RewardAlreadyDisputed
+            );
+
+            DisputedRewards::<T>::insert(
+                survey_id,
+                &participant_id,
+                frame_system::Pallet::<T>::block_number(),
+            );
+
+            Self::emit_event(Event::RewardDisputed {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve the dispute of the reward paid to a participant
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `participant_id`: the address of the rewarded participant
+        /// - `upheld`: whether the reward is clawed back, as by `clawback_reward`, rather than
+        ///   kept by the participant
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Reward should be disputed.
+        /// REQUIRES: If upheld, same as `clawback_reward`, `ClawbackWindow` aside.
+        ///
+        /// Emits `DisputeResolved`
+        #[pallet::call_index(53)]
+        #[pallet::weight(u64::default())]
+        pub fn resolve_dispute(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+            upheld: bool,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "resolve_dispute: survey_id = {:?}, caller = {:?}, participant_id = {:?}, upheld = {}",
+                survey_id, caller, participant_id, upheld
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            ensure!(
+                DisputedRewards::<T>::contains_key(survey_id, &participant_id),
+                Error::<T>::RewardNotDisputed
+            );
+
+            let clawed_back = if upheld {
+                Some(Self::do_clawback(survey_id, &caller, &participant_id, false)?)
+            } else {
+                DisputedRewards::<T>::remove(survey_id, &participant_id);
+                None
+            };
+
+            Self::emit_event(Event::DisputeResolved {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+                upheld,
+                clawed_back,
+            });
+
+            Ok(())
+        }
+
+        /// Set the rounding of the reward of a survey
+        ///
+        /// - `survey_id`: the off-chain computed unique id of the survey
+        /// - `rounding`: whether the reward is rounded down or up when the funding does not
+        ///   divide evenly between participants
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Can only be called by survey owner.
+        /// REQUIRES: Survey should not be already funded.
+        ///
+        /// Emits `SurveyRewardRoundingSet`
+        #[pallet::call_index(47)]
+        #[pallet::weight(u64::default())]
+        pub fn set_reward_rounding(
+            origin: OriginFor<T>,
+            survey_id: T::SurveyId,
+            rounding: RewardRounding,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "set_reward_rounding: survey_id = {:?}, caller = {:?}, rounding = {:?}",
+                survey_id, caller, rounding
+            );
+
+            let survey = SurveysMap::<T>::get(survey_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(survey.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            // Reward is computed once funded
+            ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+            SurveysMap::<T>::insert(survey_id, Survey { rounding, ..survey });
+
+            Self::emit_event(Event::SurveyRewardRoundingSet {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                rounding,
+            });
+
+            Ok(())
+        }
+
+        /// Create a new survey with the parameters of an existing survey of the caller
+        ///
+        /// - `source_id`: the id of the survey to copy
+        /// - `new_id`: the off-chain computed unique id of the new survey
+        ///
+        /// Participants, funding, status and deadline are not copied: the new survey starts
+        /// active, unfunded and without participants nor deadline.
+        ///
+        /// REQUIRES: Source survey has to be created already.
+        /// REQUIRES: Can only be called by the owner of the source survey.
+        /// REQUIRES: New survey must not have been crated already
+        ///
+        /// Emits `SurveyCreated`
+        #[pallet::call_index(48)]
+        #[pallet::weight(u64::default())]
+        pub fn clone_survey(
+            origin: OriginFor<T>,
+            source_id: T::SurveyId,
+            new_id: T::SurveyId,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            log::debug!(
+                target: LOG_TARGET,
+                "clone_survey: source_id = {:?}, new_id = {:?}, caller = {:?}",
+                source_id, new_id, caller
+            );
+
+            let source = SurveysMap::<T>::get(source_id).ok_or(Error::<T>::SurveyNotCreated)?;
+
+            // Check that caller is owner
+            ensure!(source.owner_id == caller, Error::<T>::NotOwnerOfSurvey);
+
+            Self::do_create_survey(caller, new_id, source.participants_limit, None)?;
+
+            SurveysMap::<T>::mutate(new_id, |maybe_survey| {
+                if let Some(survey) = maybe_survey {
+                    survey.vesting_blocks = source.vesting_blocks;
+                    survey.early_bird_count = source.early_bird_count;
+                    survey.early_bird_multiplier = source.early_bird_multiplier;
+                    survey.is_public = source.is_public;
+                    survey.post_hoc_rewards = source.post_hoc_rewards;
+                    survey.referral_reward = source.referral_reward;
+                    survey.min_participants = source.min_participants;
+                    survey.distribute_remainder = source.distribute_remainder;
+                    survey.distribution = source.distribution;
+                    survey.recompute_reward_on_extend = source.recompute_reward_on_extend;
+                    survey.rounding = source.rounding;
+                }
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Create a survey owned by `owner_id`.
+        ///
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Survey must not have been crated already
+        /// REQUIRES: Owner should have enough free balance for the deposit.
+        ///
+        /// Emits `SurveyCreated`
+        fn do_create_survey(
+            owner_id: OwnerId<T>,
+            survey_id: T::SurveyId,
+            participants_limit: BalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            // Check that pallet is not paused
+            Self::ensure_not_paused()?;
+
+            // Check if survey is not already created, archived surveys keeping their id
+            ensure!(
+                SurveysMap::<T>::get(survey_id).is_none() && !Archive::<T>::contains_key(survey_id),
+                Error::<T>::SurveyAlreadyCreated
+            );
+
+            // Check that the survey has room for participants, its reward being divided between them
+            ensure!(!participants_limit.is_zero(), Error::<T>::ZeroParticipantsLimit);
+
+            // Take the creation deposit from the owner
+            let deposit = T::SurveyDeposit::get();
+            if !deposit.is_zero() {
+                let new_owner_balance = Self::debited_balance(
+                    &owner_id,
+                    deposit,
+                    Error::<T>::NotEnoughBalanceForDeposit,
+                )?;
+                let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                    &owner_id,
+                    new_owner_balance,
+                );
+            }
+
+            // Charge the creation fee, burnt unless it has a destination
+            let fee = T::CreationFee::get();
+            if !fee.is_zero() {
+                let new_owner_balance =
+                    Self::debited_balance(&owner_id, fee, Error::<T>::CannotAffordCreationFee)?;
+                let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                    &owner_id,
+                    new_owner_balance,
+                );
+                if let Some(destination) = T::FeeDestination::get() {
+                    Self::credit(&destination, fee, survey_id)?;
+                }
+            }
+
+            // Create the survey
+            let new_survey = Survey {
+                survey_id,
+                owner_id: owner_id.clone(),
+                participants_limit,
+                number_participants: 0u32.into(),
+                rewarded_count: 0u32.into(),
+                is_funded: false,
+                funded_amount: None,
+                reward_amount: None,
+                status: Status::Active,
+                deadline,
+                vesting_blocks: None,
+                early_bird_count: 0u32.into(),
+                early_bird_multiplier: 1,
+                reclaimable_surplus: 0u32.into(),
+                is_public: true,
+                funder_id: None,
+                funded_at: None,
+                completed_at: None,
+                status_changed_by: None,
+                total_paid: 0u32.into(),
+                deposit,
+                bonus: None,
+                bonus_escrow: 0u32.into(),
+                escrow_remaining: 0u32.into(),
+                post_hoc_rewards: false,
+                referral_reward: 0u32.into(),
+                min_participants: 0u32.into(),
+                distribute_remainder: false,
+                reward_remainder: 0u32.into(),
+                distribution: DistributionOrder::Fifo,
+                recompute_reward_on_extend: false,
+                rounding: RewardRounding::Floor,
+                creation_flow: CreationFlow::Separate,
+            };
+
+            SurveysMap::<T>::insert(survey_id, new_survey);
+            SurveyCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            if let Some(deadline) = deadline {
+                Self::schedule_expiry(survey_id, deadline);
+            }
+            T::OnSurveyLifecycle::on_created(survey_id, &owner_id);
+
+            // Keep auto-generated ids past manual ones
+            NextSurveyId::<T>::mutate(|next_survey_id| {
+                if survey_id >= *next_survey_id {
+                    *next_survey_id = survey_id.saturating_add(One::one());
+                }
+            });
+
+            Self::emit_event(Event::SurveyCreated {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                owner_id,
+            });
+
+            Ok(())
+        }
+
+        /// Fund a survey from the balance of `caller`, recorded as its funder.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Survey should not be already funded.
+        /// REQUIRES: Survey should be active, a cancelled survey never refunding its escrow.
+        /// REQUIRES: Caller should have enough free balance.
+        /// REQUIRES: Can only be called by survey owner if `owner_only`.
+        ///
+        /// Emits `SurveyFunded`
+        fn do_fund_survey(
+            caller: AccountId<T>,
+            survey_id: T::SurveyId,
+            fund_amount: BalanceOf<T>,
+            owner_only: bool,
+        ) -> DispatchResult {
+            let survey_option = SurveysMap::<T>::get(survey_id);
+
+            // Check that survey is created
+            match survey_option {
+                None => Err(Error::<T>::SurveyNotCreated.into()),
+                Some(survey) => {
+                    // Check that pallet is not paused
+                    Self::ensure_not_paused()?;
+
+                    // Check that caller is owner
+                    ensure!(
+                        !owner_only || survey.owner_id == caller,
+                        Error::<T>::NotOwnerOfSurvey
+                    );
+
+                    // Check that survey is not already funded
+                    ensure!(!survey.is_funded, Error::<T>::SurveyAlreadyFunded);
+
+                    Self::ensure_active(&survey)?;
+
+                    // Check that funding amount is superior to participants_limit (otherwise reward_amount will be equal to 0)
+                    ensure!(
+                        survey.participants_limit <= fund_amount,
+                        Error::<T>::FundingInferiorNumberParticipants
+                    );
+
+                    // Check that funding amount reaches the configured minimum
+                    ensure!(
+                        fund_amount >= T::MinFunding::get(),
+                        Error::<T>::FundingBelowMinimum
+                    );
+
+                    // Check that accumulated funding and escrow cannot overflow once stored
+                    let funded_amount = survey
+                        .funded_amount
+                        .unwrap_or_default()
+                        .checked_add(&fund_amount)
+                        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+                    let escrow_remaining = survey
+                        .escrow_remaining
+                        .checked_add(&fund_amount)
+                        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+
+                    // Set aside the referral reward of every participant
+                    let rewards_fund = fund_amount
+                        .checked_sub(&Self::referral_budget(&survey)?)
+                        .ok_or(Error::<T>::FundingInsufficientForReferrals)?;
+
+                    // Check that funding amount covers the early bird bonus (otherwise reward_amount will be equal to 0)
+                    let reward_slots = Self::reward_slots(&survey)?;
+                    ensure!(
+                        reward_slots <= rewards_fund,
+                        Error::<T>::FundingInsufficientForEarlyBird
+                    );
+
+                    // Check that funder has enough balance for funding, out of the fundable balance
+                    // which excludes held and frozen funds as well as funds already escrowed by
+                    // other surveys, so that they cannot be committed twice
+                    let new_funder_balance = Self::debited_balance(
+                        &caller,
+                        fund_amount,
+                        Error::<T>::NotEnoughBalanceForFunding,
+                    )?;
+
+                    // Check that owner has enough of the bonus asset for every participant
+                    let bonus_escrow = match survey.bonus {
+                        None => 0u32.into(),
+                        Some((asset_id, bonus_amount)) => {
+                            let limit: u128 = survey.participants_limit.saturated_into();
+                            let bonus_escrow = bonus_amount
+                                .checked_mul(&limit.saturated_into())
+                                .ok_or(Error::<T>::NotEnoughBonusForFunding)?;
+                            let bonus_balance =
+                                <T::BonusAssets as fungibles::Inspect<AccountId<T>>>::reducible_balance(
+                                    asset_id,
+                                    &survey.owner_id,
+                                    Preservation::Expendable,
+                                    Fortitude::Polite,
+                                );
+                            ensure!(bonus_balance >= bonus_escrow, Error::<T>::NotEnoughBonusForFunding);
+                            bonus_escrow
+                        }
+                    };
+
+                    // Compute reward amount
+                    let reward_amount = rewards_fund
+                        .checked_div(&reward_slots)
+                        .ok_or(Error::<T>::DefensiveErrorWhenDividing)
+                        .map_err(|e| Self::defensive_error(e, survey_id, &caller))?;
+
+                    // Round up when the funding does not divide evenly, which requires funding
+                    // the rounded up reward of every participant
+                    let reward_amount = match survey.rounding {
+                        RewardRounding::Floor => reward_amount,
+                        RewardRounding::Ceil => {
+                            let reward_amount =
+                                if reward_amount.saturating_mul(reward_slots) < rewards_fund {
+                                    reward_amount.saturating_add(1u32.into())
+                                } else {
+                                    reward_amount
+                                };
+                            ensure!(
+                                reward_amount.saturating_mul(reward_slots) <= rewards_fund,
+                                Error::<T>::FundingInsufficientForCeilReward
+                            );
+                            reward_amount
+                        }
+                    };
+
+                    // Check that committed rewards do not exceed funding
+                    Self::ensure_rewards_covered(reward_amount, reward_slots, rewards_fund)?;
+
+                    // Check that the reward is not dust, unless only known once finalized
+                    ensure!(
+                        survey.post_hoc_rewards || reward_amount >= T::MinReward::get(),
+                        Error::<T>::RewardBelowMinimum
+                    );
+
+                    // Hand the remainder out one unit at a time to the first participants, which
+                    // escrow covers as it is what the division leaves over
+                    let reward_remainder = if survey.distribute_remainder {
+                        rewards_fund.saturating_sub(reward_amount.saturating_mul(reward_slots))
+                    } else {
+                        0u32.into()
+                    };
+
+                    // Post-hoc rewards are only known once participants are final
+                    let reward_amount = (!survey.post_hoc_rewards).then_some(reward_amount);
+                    if let Some(reward_amount) = reward_amount {
+                        let highest_base_reward = if reward_remainder.is_zero() {
+                            reward_amount
+                        } else {
+                            reward_amount.saturating_add(1u32.into())
+                        };
+                        Self::ensure_within_reward_cap(&survey, highest_base_reward)?;
+                    }
+
+                    // Nothing is written before this point. The funds are taken before the survey
+                    // is marked funded, and every write is rolled back together should one of them
+                    // fail, so that a survey is never left funded without its funds nor the other
+                    // way round, even when not called from a dispatchable
+                    frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                        // Take the funding from the funder, which cannot fail once its balance
+                        // is checked
+                        let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                            &caller,
+                            new_funder_balance,
+                        );
+
+                        // Take the bonus asset of every participant from the owner
+                        if let Some((asset_id, _)) = survey.bonus {
+                            <T::BonusAssets as fungibles::Mutate<AccountId<T>>>::burn_from(
+                                asset_id,
+                                &survey.owner_id,
+                                bonus_escrow,
+                                Precision::Exact,
+                                Fortitude::Polite,
+                            )?;
+                        }
+
+                        // Check that the pallet does not hold more than `MaxTotalEscrow`
+                        Self::reserve_total_escrow(fund_amount)?;
+
+                        // Fund survey
+                        let funded_survey = Survey {
+                            is_funded: true,
+                            funded_amount: Some(funded_amount),
+                            reward_amount,
+                            funder_id: Some(caller.clone()),
+                            funded_at: Some(frame_system::Pallet::<T>::block_number()),
+                            escrow_remaining,
+                            bonus_escrow,
+                            reward_remainder,
+                            ..survey
+                        };
+                        SurveysMap::<T>::insert(survey_id, funded_survey);
+                        if let Some(reward_amount) = reward_amount {
+                            RewardAmounts::<T>::insert(survey_id, reward_amount);
+                        }
+
+                        Ok(())
+                    })?;
+                    T::OnSurveyLifecycle::on_funded(survey_id, &caller);
+
+                    Self::emit_event(Event::SurveyFunded {
+                        survey_id,
+                        seq: Self::next_event_seq(survey_id),
+                        funded_amount: fund_amount,
+                        funder_id: caller,
+                    });
+
+                    Ok(())
+                }
+            }
+        }
+
+        /// Whether a participant is registered to a survey.
+        pub fn is_participant(survey_id: T::SurveyId, participant_id: ParticipantId<T>) -> bool {
+            if T::DenseParticipants::get() {
+                ParticipantSets::<T>::get(survey_id).contains(&participant_id)
+            } else {
+                Participants::<T>::get(survey_id, participant_id)
+            }
+        }
+
+        /// At most `limit` participants of a survey, reading no more of them, starting after
+        /// `after` in storage order.
+        fn participants_after(
+            survey_id: T::SurveyId,
+            after: Option<ParticipantId<T>>,
+            limit: usize,
+        ) -> Vec<ParticipantId<T>> {
+            if T::DenseParticipants::get() {
+                ParticipantSets::<T>::get(survey_id)
+                    .into_iter()
+                    .filter(|participant_id| {
+                        after.as_ref().map_or(true, |after| participant_id > after)
+                    })
+                    .take(limit)
+                    .collect()
+            } else {
+                match after {
+                    None => Participants::<T>::iter_key_prefix(survey_id).take(limit).collect(),
+                    Some(after) => Participants::<T>::iter_key_prefix_from(
+                        survey_id,
+                        Participants::<T>::hashed_key_for(survey_id, after),
+                    )
+                    .take(limit)
+                    .collect(),
+                }
+            }
+        }
+
+        /// Remove at most `limit` participants of a survey, reading no more of them, and return
+        /// them.
+        fn drain_participants(survey_id: T::SurveyId, limit: usize) -> Vec<ParticipantId<T>> {
+            if T::DenseParticipants::get() {
+                let mut participants = ParticipantSets::<T>::get(survey_id);
+                let drained: Vec<ParticipantId<T>> =
+                    participants.iter().take(limit).cloned().collect();
+                for participant_id in drained.iter() {
+                    participants.remove(participant_id);
+                }
+                if participants.is_empty() {
+                    ParticipantSets::<T>::remove(survey_id);
+                } else {
+                    ParticipantSets::<T>::insert(survey_id, participants);
+                }
+                drained
+            } else {
+                let drained: Vec<ParticipantId<T>> =
+                    Participants::<T>::iter_key_prefix(survey_id).take(limit).collect();
+                for participant_id in drained.iter() {
+                    Participants::<T>::remove(survey_id, participant_id);
+                }
+                drained
+            }
+        }
+
+        /// Remove at most `limit` entries of `survey_id` from the double map `M`, returning the
+        /// number removed.
+        fn remove_prefix_bounded<M, K2, V>(survey_id: T::SurveyId, limit: usize) -> usize
+        where
+            K2: FullCodec,
+            V: FullCodec,
+            M: IterableStorageDoubleMap<T::SurveyId, K2, V>,
+        {
+            let keys: Vec<K2> = M::iter_key_prefix(survey_id).take(limit).collect();
+            for key in keys.iter() {
+                M::remove(survey_id, key);
+            }
+            keys.len()
+        }
+
+        /// Remove at most `MaxClear` participants of an archived survey with their entries, then
+        /// at most `MaxClear` entries left in its other maps, such as those of participants
+        /// unregistered after being rewarded. Returns whether every entry is removed.
+        fn clear_archived_entries(survey_id: T::SurveyId) -> bool {
+            let max_clear = T::MaxClear::get() as usize;
+
+            let participants = Self::drain_participants(survey_id, max_clear);
+            for participant_id in participants.iter() {
+                JoinedSurveys::<T>::remove(participant_id, survey_id);
+                ParticipantsRewarded::<T>::remove(survey_id, participant_id);
+                RewardReceipts::<T>::remove(survey_id, participant_id);
+                DisputedRewards::<T>::remove(survey_id, participant_id);
+                ParticipantScores::<T>::remove(survey_id, participant_id);
+                ParticipantResponses::<T>::remove(survey_id, participant_id);
+                RegistrationIndex::<T>::remove(survey_id, participant_id);
+            }
+            if participants.len() == max_clear {
+                return false;
+            }
+
+            let mut budget = max_clear;
+            budget -=
+                Self::remove_prefix_bounded::<ParticipantsRewarded<T>, _, _>(survey_id, budget);
+            budget -= Self::remove_prefix_bounded::<RewardReceipts<T>, _, _>(survey_id, budget);
+            budget -= Self::remove_prefix_bounded::<DisputedRewards<T>, _, _>(survey_id, budget);
+            budget -= Self::remove_prefix_bounded::<ParticipantScores<T>, _, _>(survey_id, budget);
+            budget -=
+                Self::remove_prefix_bounded::<ParticipantResponses<T>, _, _>(survey_id, budget);
+            budget -= Self::remove_prefix_bounded::<RegistrationIndex<T>, _, _>(survey_id, budget);
+            budget -= Self::remove_prefix_bounded::<Managers<T>, _, _>(survey_id, budget);
+            budget > 0
+        }
+
+        /// Participants registered to a survey.
+        pub fn participants_of(survey_id: T::SurveyId) -> Vec<ParticipantId<T>> {
+            if T::DenseParticipants::get() {
+                ParticipantSets::<T>::get(survey_id).into_iter().collect()
+            } else {
+                Participants::<T>::iter_prefix(survey_id)
+                    .filter(|(_, is_participant)| *is_participant)
+                    .map(|(participant_id, _)| participant_id)
+                    .collect()
+            }
+        }
+
+        /// Add a participant to the participants of a survey.
+        fn insert_participant(
+            survey_id: T::SurveyId,
+            participant_id: &ParticipantId<T>,
+        ) -> Result<(), Error<T>> {
+            if T::DenseParticipants::get() {
+                ParticipantSets::<T>::try_mutate(survey_id, |participants| {
+                    participants
+                        .try_insert(participant_id.clone())
+                        .map(|_| ())
+                        .map_err(|_| Error::<T>::MaxNumberOfParticipantsReached)
+                })
+            } else {
+                Participants::<T>::insert(survey_id, participant_id, true);
+                Ok(())
+            }
+        }
+
+        /// Remove a participant from the participants of a survey.
+        fn remove_participant(survey_id: T::SurveyId, participant_id: &ParticipantId<T>) {
+            if T::DenseParticipants::get() {
+                ParticipantSets::<T>::mutate(survey_id, |participants| {
+                    participants.remove(participant_id);
+                });
+            } else {
+                Participants::<T>::remove(survey_id, participant_id);
+            }
+        }
+
+        /// Set the status of a survey on behalf of `caller`.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: Survey should not be cancelled.
+        ///
+        /// Emits `SurveyStatusUpdated`
+        fn do_set_survey_status(
+            caller: AccountId<T>,
+            survey_id: T::SurveyId,
+            new_status: Status,
+        ) -> DispatchResult {
+            let is_completing = Self::mutate_survey(survey_id, |survey| {
+                // Check that pallet is not paused
+                Self::ensure_not_paused()?;
+
+                // Check that caller is owner or manager
+                Self::ensure_owner_or_manager(survey, &caller)?;
+
+                // Check that survey is not cancelled, which is final
+                ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+                // Check that new status is not Cancelled, which would leave the escrow and the
+                // deposit unsettled
+                ensure!(new_status != Status::Cancelled, Error::<T>::CannotSetCancelledStatus);
+
+                let is_completing =
+                    new_status == Status::Completed && survey.status != Status::Completed;
+
+                // Set new status, completing the survey on the first completion
+                if is_completing {
+                    Self::do_finalize(survey, Some(&caller))?;
+                } else {
+                    survey.status = new_status.clone();
+                    survey.status_changed_by = Some(caller.clone());
+                }
+
+                Ok(is_completing)
+            })?;
+
+            if is_completing {
+                T::OnSurveyLifecycle::on_completed(survey_id);
+            }
+
+            // Emit event
+            Self::emit_event(Event::SurveyStatusUpdated {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                status_code: new_status.code(),
+                new_status,
+                changed_by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Register `participant_id` to a survey on behalf of `caller`.
+        ///
+        /// REQUIRES: Survey has to be created already.
+        /// REQUIRES: Pallet should not be paused.
+        /// REQUIRES: Can only be called by survey owner or one of its managers.
+        /// REQUIRES: Survey should be funded and active.
+        /// REQUIRES: Deadline of the survey should not have passed.
+        /// REQUIRES: Survey should not be full.
+        /// REQUIRES: Participant should not be the zero account.
+        /// REQUIRES: Participant should not be already registered.
+        /// REQUIRES: Participant should be eligible per `ParticipantEligibility`.
+        ///
+        /// Emits `NewParticipantRegistered`
+        fn do_register_participant(
+            caller: AccountId<T>,
+            survey_id: T::SurveyId,
+            participant_id: ParticipantId<T>,
+        ) -> DispatchResult {
+            Self::mutate_survey(survey_id, |survey| {
+                // Check that pallet is not paused
+                Self::ensure_not_paused()?;
+
+                // Check that caller is owner or manager
+                Self::ensure_owner_or_manager(survey, &caller)?;
+
+                // Check that survey is already funded
+                ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
+
+                Self::ensure_active(survey)?;
+
+                // Check that the deadline has not passed, even if the survey is not swept yet
+                ensure!(
+                    !Self::is_expired(survey, frame_system::Pallet::<T>::block_number()),
+                    Error::<T>::SurveyExpired
+                );
+
+                // Check that we have not reached max number of participants already, before the
+                // participant checks as it is the common failure and needs no participant storage
+                // read. The count is incremented below within the same mutation, so every
+                // registration sees the previous ones of the block
+                ensure!(
+                    survey.number_participants < survey.participants_limit,
+                    Error::<T>::MaxNumberOfParticipantsReached
+                );
+
+                // Check that participant is not the zero account
+                ensure!(
+                    !Self::is_zero_account(&participant_id),
+                    Error::<T>::InvalidParticipant
+                );
+
+                // Check that participant is not already registered
+                ensure!(
+                    !Self::is_participant(survey_id, participant_id.clone()),
+                    Error::<T>::ParticipantAlreadyRegistered
+                );
+
+                // Check that participant meets the conditions of the runtime
+                ensure!(
+                    T::ParticipantEligibility::is_eligible(&participant_id, survey_id),
+                    Error::<T>::ParticipantNotEligible
+                );
+
+                // Update participants storage unit
+                Self::insert_participant(survey_id, &participant_id)?;
+                JoinedSurveys::<T>::insert(&participant_id, survey_id, ());
+                RegistrationIndex::<T>::insert(
+                    survey_id,
+                    participant_id.clone(),
+                    Self::next_registration_index(survey_id),
+                );
+
+                // Update number of participant on survey
+                survey.number_participants =
+                    arithmetic::add_participant_count(survey.number_participants)?;
+
+                Ok(())
+            })?;
+
+            Self::emit_event(Event::NewParticipantRegistered {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                participant_id,
+            });
+
+            Ok(())
+        }
+
+        /// Pay `reward_amount` to a participant of `survey` out of its escrow and record it.
+        ///
+        /// If the participant would stay below the existential deposit, the reward is either
+        /// topped up to it or skipped, leaving the participant unrewarded, per `ForceCreateAccounts`.
+        ///
+        /// Emits `RewardClaimed`, `RewardSkippedBelowED`, or `RewardFailedDefensive` if the escrow
+        /// cannot cover the reward. A defensive failure leaves the participant unrewarded without
+        /// failing, so that the event is not reverted with the dispatchable.
+        pub(crate) fn do_pay_reward(
+            survey: &Survey<T>,
+            participant_id: ParticipantId<T>,
+            reward_amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let survey_id = survey.survey_id;
+
+            if let Some(reward_amount) =
+                Self::do_pay_reward_silently(survey, participant_id.clone(), reward_amount)?
+            {
+                Self::emit_event(Event::RewardClaimed {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                    participant_id,
+                    reward_amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Same as `do_pay_reward`, without emitting `RewardClaimed`.
+        ///
+        /// `RewardFee` of the reward goes to `TreasuryAccount`, escrow paying the gross reward.
+        ///
+        /// Returns the amount paid to the participant, or `None` if the reward was skipped or
+        /// failed defensively.
+        fn do_pay_reward_silently(
+            survey: &Survey<T>,
+            participant_id: ParticipantId<T>,
+            reward_amount: BalanceOf<T>,
+        ) -> Result<Option<BalanceOf<T>>, DispatchError> {
+            let survey_id = survey.survey_id;
+
+            let Some((reward_amount, fee)) = Self::reward_payment(&participant_id, reward_amount)
+            else {
+                let fee = T::RewardFee::get() * reward_amount;
+                Self::emit_event(Event::RewardSkippedBelowED {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                    participant_id,
+                    reward_amount: reward_amount.saturating_sub(fee),
+                });
+                return Ok(None);
+            };
+
+            // Check that escrow covers the reward, reading it from storage as `survey` may be
+            // stale when several participants are paid in a row. Every reward path goes through
+            // here, so the stored escrow is the only one checked and decremented, and rewards paid
+            // by earlier calls of the block can never be paid out of it again
+            let (escrow_remaining, bonus_escrow) = Self::get_survey(survey_id)
+                .map(|survey| (survey.escrow_remaining, survey.bonus_escrow))
+                .unwrap_or_default();
+            let gross_amount = reward_amount.saturating_add(fee);
+            let Ok(new_escrow_remaining) = arithmetic::sub_escrow::<T, _>(escrow_remaining, gross_amount)
+            else {
+                // Unlike other defensive errors, this one is surfaced to watchers with an event,
+                // which is only kept on-chain if the call succeeds
+                log::error!(
+                    target: LOG_TARGET,
+                    "defensive error happened: {:?}, survey_id = {:?}, account = {:?}",
+                    Error::<T>::DefensiveNotEnoughFundsInSurveyForReward, survey_id, participant_id
+                );
+                Self::emit_event(Event::RewardFailedDefensive {
+                    survey_id,
+                    seq: Self::next_event_seq(survey_id),
+                    participant_id,
+                });
+                return Ok(None);
+            };
+
+            // The participant is marked rewarded before being paid, both being rolled back together
+            // should the payment fail, so that a participant can neither be paid twice nor be left
+            // flagged without being paid
+            frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                // Update reward storage unit
+                ParticipantsRewarded::<T>::insert(survey_id, participant_id.clone(), true);
+
+                // Reward participant, a failed mint rolling the whole payment back
+                <T::NativeBalance as fungible::Mutate<AccountId<T>>>::mint_into(
+                    &participant_id,
+                    reward_amount,
+                )?;
+
+                // Pay the cut of the treasury
+                Self::credit(&T::TreasuryAccount::get(), fee, survey_id)?;
+
+                // Lock reward under a vesting schedule if the survey requires it
+                if let Some(vesting_blocks) = survey.vesting_blocks {
+                    T::VestingHandler::vest_reward(&participant_id, reward_amount, vesting_blocks)?;
+                }
+
+                // Pay the bonus asset on top of the reward
+                let mut new_bonus_escrow = bonus_escrow;
+                if let Some((asset_id, bonus_amount)) = survey.bonus {
+                    new_bonus_escrow = arithmetic::sub_escrow(bonus_escrow, bonus_amount)
+                        .map_err(|e| Self::defensive_error(e, survey_id, &participant_id))?;
+                    <T::BonusAssets as fungibles::Mutate<AccountId<T>>>::mint_into(
+                        asset_id,
+                        &participant_id,
+                        bonus_amount,
+                    )?;
+                }
+
+                // Update survey escrow and rewarded count
+                SurveysMap::<T>::mutate(survey_id, |survey| {
+                    if let Some(survey) = survey {
+                        survey.escrow_remaining = new_escrow_remaining;
+                        survey.bonus_escrow = new_bonus_escrow;
+                        survey.rewarded_count = survey.rewarded_count.saturating_add(1u32.into());
+                        survey.total_paid = survey.total_paid.saturating_add(gross_amount);
+                    }
+                });
+
+                Self::release_total_escrow(gross_amount);
+
+                // Update lifetime rewards of participant
+                LifetimeRewards::<T>::mutate(&participant_id, |total| {
+                    *total = total.saturating_add(reward_amount)
+                });
+
+                RewardReceipts::<T>::insert(
+                    survey_id,
+                    &participant_id,
+                    RewardReceipt {
+                        amount: reward_amount,
+                        fee,
+                        block: frame_system::Pallet::<T>::block_number(),
+                    },
+                );
+
+                BlockRewards::<T>::mutate(|(total_rewards_paid, participants_paid)| {
+                    *total_rewards_paid = total_rewards_paid.saturating_add(reward_amount);
+                    *participants_paid = participants_paid.saturating_add(1);
+                });
+
+                Ok(())
+            })?;
+
+            Ok(Some(reward_amount))
+        }
+
+        /// Split `reward_amount` owed to a participant into the amount paid to the participant and
+        /// the cut of `RewardFee` paid to the treasury, escrow paying both.
+        ///
+        /// A reward leaving the participant below the existential deposit is either topped up to
+        /// it or skipped, returning `None`, per `ForceCreateAccounts`.
+        fn reward_payment(
+            participant_id: &ParticipantId<T>,
+            reward_amount: BalanceOf<T>,
+        ) -> Option<(BalanceOf<T>, BalanceOf<T>)> {
+            // The treasury takes its cut of the reward, the participant being paid the rest
+            let fee = T::RewardFee::get() * reward_amount;
+            let reward_amount = reward_amount.saturating_sub(fee);
+
+            let participant_balance: BalanceOf<T> =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(participant_id);
+
+            // A reward leaving the participant below the existential deposit would not be credited
+            let minimum_balance =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::minimum_balance();
+            if participant_balance.saturating_add(reward_amount) >= minimum_balance {
+                Some((reward_amount, fee))
+            } else if T::ForceCreateAccounts::get() {
+                Some((minimum_balance.saturating_sub(participant_balance), fee))
+            } else {
+                None
+            }
+        }
+
+        /// Escrow owed to the registered participants of `survey` not rewarded yet, each costing
+        /// the escrow `do_pay_reward` would take, early bird bonus, remainder and fee included.
+        /// Referral rewards being paid at registration, none is owed for registered participants.
+        ///
+        /// The whole escrow is owed while the reward of a participant is not known yet.
+        fn owed_escrow(survey: &Survey<T>) -> BalanceOf<T> {
+            let survey_id = survey.survey_id;
+            let mut owed: BalanceOf<T> = 0u32.into();
+            for participant_id in Self::participants_of(survey_id) {
+                if Self::is_participant_already_rewarded(survey_id, participant_id.clone()) {
+                    continue;
+                }
+                let Ok(reward_amount) = Self::participant_reward(survey, &participant_id) else {
+                    return survey.escrow_remaining;
+                };
+                if let Some((reward_amount, fee)) =
+                    Self::reward_payment(&participant_id, reward_amount)
+                {
+                    owed = owed.saturating_add(reward_amount).saturating_add(fee);
+                }
+            }
+            owed
+        }
+
+        /// Add `amount`, released by survey `survey_id`, to the balance of `who`.
+        ///
+        /// Fails with `PayoutFailed` if the amount cannot be minted to `who`.
+        fn credit(
+            who: &AccountId<T>,
+            amount: BalanceOf<T>,
+            survey_id: T::SurveyId,
+        ) -> Result<(), Error<T>> {
+            if amount.is_zero() {
+                return Ok(());
+            }
+
+            <T::NativeBalance as fungible::Mutate<AccountId<T>>>::mint_into(who, amount).map_err(
+                |e| {
+                    log::error!(
+                        target: LOG_TARGET,
+                        "credit failed: {:?}, survey_id = {:?}, account = {:?}",
+                        e, survey_id, who
+                    );
+                    Error::<T>::PayoutFailed
+                },
+            )?;
+
+            Ok(())
+        }
+
+        /// Balance of `who` once `amount` is taken from it, failing with `error` if `amount` is
+        /// above the balance `FundingSource` allows taking. Every debit of an owner or funder
+        /// goes through here.
+        fn debited_balance(
+            who: &AccountId<T>,
+            amount: BalanceOf<T>,
+            error: Error<T>,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            let balance: BalanceOf<T> =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(who);
+            balance
+                .checked_sub(&amount)
+                .filter(|_| T::FundingSource::fundable_balance(who) >= amount)
+                .ok_or(error)
+        }
+
+        /// Burn `amount` from the balance of `who`, failing if it is not reducible.
+        fn take_back(who: &AccountId<T>, amount: BalanceOf<T>) -> Result<(), Error<T>> {
+            if amount.is_zero() {
+                return Ok(());
+            }
+
+            let reducible_balance =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::reducible_balance(
+                    who,
+                    Preservation::Expendable,
+                    Fortitude::Polite,
+                );
+            ensure!(reducible_balance >= amount, Error::<T>::ClawbackFailed);
+            let balance: BalanceOf<T> =
+                <T::NativeBalance as fungible::Inspect<AccountId<T>>>::balance(who);
+            let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                who,
+                balance.saturating_sub(amount),
+            );
+
+            Ok(())
+        }
+
+        /// Reward of every participant of a post-hoc survey, splitting its funding between its
+        /// actual participants.
+        fn post_hoc_reward(
+            survey: &Survey<T>,
+            caller: &AccountId<T>,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            // Compute reward amount from the actual participants, the referral rewards being set
+            // aside at funding
+            let funded_amount = survey
+                .funded_amount
+                .unwrap_or_default()
+                .saturating_sub(Self::referral_budget(survey)?);
+            let reward_amount = funded_amount
+                .checked_div(&survey.number_participants)
+                .ok_or(Error::<T>::DefensiveErrorWhenDividing)
+                .map_err(|e| Self::defensive_error(e, survey.survey_id, caller))?;
+
+            // Check that committed rewards do not exceed funding
+            Self::ensure_rewards_covered(reward_amount, survey.number_participants, funded_amount)?;
+            Self::ensure_within_reward_cap(survey, reward_amount)?;
+
+            Ok(reward_amount)
+        }
+
+        /// Complete `survey`, which closes its registration, on behalf of `caller` or of no one if
+        /// it expired.
+        ///
+        /// The reward of a funded post-hoc survey is computed from its actual participants, if
+        /// not already, so that they can then be rewarded individually. Every path completing a
+        /// survey goes through here, but `finalize_rewards`, which also pays the rewards.
+        fn do_finalize(
+            survey: &mut Survey<T>,
+            caller: Option<&AccountId<T>>,
+        ) -> Result<(), Error<T>> {
+            survey.status = Status::Completed;
+            survey.status_changed_by = caller.cloned();
+            survey.completed_at = Some(frame_system::Pallet::<T>::block_number());
+
+            if survey.is_funded
+                && survey.post_hoc_rewards
+                && survey.reward_amount.is_none()
+                && !survey.number_participants.is_zero()
+            {
+                let owner_id = survey.owner_id.clone();
+                let reward_amount = Self::post_hoc_reward(survey, caller.unwrap_or(&owner_id))?;
+                survey.reward_amount = Some(reward_amount);
+                RewardAmounts::<T>::insert(survey.survey_id, reward_amount);
+            }
+
+            Self::distribute_yield(survey)
+        }
+
+        /// Share the yield accrued by the escrow of `survey` equally between its participants, on
+        /// top of their rewards. What the division leaves over is added to the escrow.
+        ///
+        /// Emits `YieldDistributed` if any yield accrued
+        fn distribute_yield(survey: &mut Survey<T>) -> Result<(), Error<T>> {
+            let survey_id = survey.survey_id;
+
+            let accrued_yield = T::YieldSource::take_accrued_yield(survey_id);
+            if accrued_yield.is_zero() {
+                return Ok(());
+            }
+
+            let participants = Self::participants_of(survey_id);
+            let participants_count = participants.len() as u32;
+            let share = accrued_yield
+                .checked_div(&participants_count.into())
+                .unwrap_or_default();
+            for participant_id in participants.iter() {
+                Self::credit(participant_id, share, survey_id)?;
+            }
+
+            let distributed = share.saturating_mul(participants_count.into());
+            let undistributed = accrued_yield.saturating_sub(distributed);
+            survey.escrow_remaining = survey.escrow_remaining.saturating_add(undistributed);
+            TotalEscrow::<T>::mutate(|total| *total = total.saturating_add(undistributed));
+
+            Self::emit_event(Event::YieldDistributed {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+                amount: distributed,
+                participants: participants_count,
+            });
+
+            Ok(())
+        }
+
+        /// Add `amount`, taken into the escrow of a survey, to `TotalEscrow`.
+        fn reserve_total_escrow(amount: BalanceOf<T>) -> Result<(), Error<T>> {
+            let total_escrow = TotalEscrow::<T>::get()
+                .checked_add(&amount)
+                .filter(|total_escrow| *total_escrow <= T::MaxTotalEscrow::get())
+                .ok_or(Error::<T>::TotalEscrowCapExceeded)?;
+            TotalEscrow::<T>::put(total_escrow);
+            Ok(())
+        }
+
+        /// Remove `amount`, released from the escrow of a survey, from `TotalEscrow`.
+        fn release_total_escrow(amount: BalanceOf<T>) {
+            TotalEscrow::<T>::mutate(|total_escrow| {
+                *total_escrow = total_escrow.saturating_sub(amount)
+            });
+        }
+
+        /// Number of rewards the funding of a survey is divided into.
+        ///
+        /// Every participant accounts for one reward, and every early bird for
+        /// `early_bird_multiplier - 1` more.
+        fn reward_slots(survey: &Survey<T>) -> Result<BalanceOf<T>, Error<T>> {
+            let early_birds = survey.early_bird_count.min(survey.participants_limit);
+            let bonus_multiplier: BalanceOf<T> = survey.early_bird_multiplier.saturating_sub(1).into();
+
+            arithmetic::mul_reward(early_birds, bonus_multiplier)?
+                .checked_add(&survey.participants_limit)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)
+        }
+
+        /// Check that `caller` can reward `participant_id` on `survey`, returning the reward owed.
+        fn ensure_rewardable(
+            survey: &Survey<T>,
+            caller: &AccountId<T>,
+            participant_id: &ParticipantId<T>,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            let survey_id = survey.survey_id;
+
+            // Check that caller is owner or manager
+            Self::ensure_owner_or_manager(survey, caller)?;
+
+            // Check that survey is already funded
+            ensure!(survey.is_funded, Error::<T>::SurveyNotFunded);
+
+            // Check that survey is not cancelled, its escrow being refunded
+            ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+            // Check that rewards are not deferred to `finalize_rewards` or completion
+            ensure!(
+                !survey.post_hoc_rewards || survey.reward_amount.is_some(),
+                Error::<T>::RewardsDeferred
+            );
+
+            // Check that the survey has enough participants to be valid
+            ensure!(
+                survey.number_participants >= survey.min_participants,
+                Error::<T>::MinimumParticipantsNotMet
+            );
+
+            Self::ensure_reward_delay_passed(survey)?;
+
+            // Check that participant is already registered
+            ensure!(
+                Self::is_participant(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantNotRegistered
+            );
+
+            // Check that participant has not already been rewarded
+            ensure!(
+                !Self::is_participant_already_rewarded(survey_id, participant_id.clone()),
+                Error::<T>::ParticipantAlreadyRewarded
+            );
+
+            let reward_amount = Self::participant_reward(survey, participant_id)
+                .map_err(|e| Self::defensive_error(e, survey_id, participant_id))?;
+
+            // Check that the reward is within the policy cap
+            ensure!(
+                reward_amount <= T::MaxRewardPerParticipant::get(),
+                Error::<T>::RewardExceedsPolicyCap
+            );
+
+            Ok(reward_amount)
+        }
+
+        /// Check that a survey is active, with a dedicated error for every other status.
+        ///
+        /// Required by the calls letting new funds, participants or responses in: funding,
+        /// registration and response updates. Payouts and owner configuration are not bound to
+        /// the survey being active.
+        fn ensure_active(survey: &Survey<T>) -> Result<(), Error<T>> {
+            match survey.status {
+                Status::Active => Ok(()),
+                Status::Paused => Err(Error::<T>::SurveyPaused),
+                Status::Completed => Err(Error::<T>::SurveyCompleted),
+                Status::Cancelled => Err(Error::<T>::SurveyCancelled),
+            }
+        }
+
+        /// Check that `RewardDelay` blocks have passed since the survey was funded.
+        fn ensure_reward_delay_passed(survey: &Survey<T>) -> Result<(), Error<T>> {
+            let rewardable_at = survey
+                .funded_at
+                .unwrap_or_default()
+                .saturating_add(T::RewardDelay::get());
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= rewardable_at,
+                Error::<T>::RewardTooEarly
+            );
+            Ok(())
+        }
+
+        /// Reward owed to a participant of a funded survey, including the early bird bonus.
+        fn participant_reward(
+            survey: &Survey<T>,
+            participant_id: &ParticipantId<T>,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            let survey_id = survey.survey_id;
+            // A funded survey always has a reward amount, unless its rewards are post-hoc and not
+            // finalized yet
+            let base_reward_amount = Self::cached_reward_amount(survey_id)
+                .ok_or(Error::<T>::DefensiveRewardAmountMissing)?;
+
+            let registration_index = Self::registration_index(survey_id, participant_id.clone());
+
+            // First registered participants share the remainder of the funding
+            let base_reward_amount = match registration_index {
+                Some(index) if index < survey.reward_remainder => base_reward_amount
+                    .checked_add(&1u32.into())
+                    .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?,
+                _ => base_reward_amount,
+            };
+
+            // First registered participants earn the early bird bonus
+            let is_early_bird = matches!(
+                registration_index,
+                Some(index) if index < survey.early_bird_count
+            );
+            let reward_amount = if is_early_bird {
+                arithmetic::mul_reward(base_reward_amount, survey.early_bird_multiplier.into())?
+            } else {
+                base_reward_amount
+            };
+
+            // Scale the reward by the score of the participant
+            let max_score = T::MaxScore::get();
+            let score = Self::participant_score(survey_id, participant_id.clone()).unwrap_or(
+                if T::UnscoredFullReward::get() { max_score } else { 0 },
+            );
+            if score == max_score {
+                return Ok(reward_amount);
+            }
+            arithmetic::mul_reward(reward_amount, score.into())?
+                .checked_div(&max_score.into())
+                .ok_or(Error::<T>::DefensiveErrorWhenDividing)
+        }
+
+        /// Check that the highest reward of a survey paying `reward_amount` to regular participants
+        /// is within `MaxRewardPerParticipant`.
+        fn ensure_within_reward_cap(
+            survey: &Survey<T>,
+            reward_amount: BalanceOf<T>,
+        ) -> Result<(), Error<T>> {
+            let highest_reward = if survey.early_bird_count.is_zero() || survey.post_hoc_rewards {
+                reward_amount
+            } else {
+                arithmetic::mul_reward(reward_amount, survey.early_bird_multiplier.into())?
+            };
+            ensure!(
+                highest_reward <= T::MaxRewardPerParticipant::get(),
+                Error::<T>::RewardExceedsPolicyCap
+            );
+            Ok(())
+        }
+
+        /// Funds set aside for paying the referral reward of every participant.
+        fn referral_budget(survey: &Survey<T>) -> Result<BalanceOf<T>, Error<T>> {
+            arithmetic::mul_reward(survey.referral_reward, survey.participants_limit)
+        }
+
+        /// Re-divide the rewards committed for the slots of `survey`, plus `added_fund`, between
+        /// the slots of `extended_survey`, returning it with its reward recomputed.
+        fn redivide_rewards(
+            survey: &Survey<T>,
+            extended_survey: Survey<T>,
+            reward_amount: BalanceOf<T>,
+            added_fund: BalanceOf<T>,
+        ) -> Result<Survey<T>, DispatchError> {
+            // Participants already rewarded were paid the former reward
+            ensure!(survey.rewarded_count.is_zero(), Error::<T>::CannotRecomputeAfterRewards);
+
+            let rewards_fund = arithmetic::mul_reward(reward_amount, Self::reward_slots(survey)?)?
+                .checked_add(&survey.reward_remainder)
+                .and_then(|rewards_fund| rewards_fund.checked_add(&added_fund))
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+            let reward_slots = Self::reward_slots(&extended_survey)?;
+            ensure!(reward_slots <= rewards_fund, Error::<T>::FundingInferiorNumberParticipants);
+
+            let reward_amount = rewards_fund
+                .checked_div(&reward_slots)
+                .ok_or(Error::<T>::DefensiveErrorWhenDividing)?;
+            Self::ensure_rewards_covered(reward_amount, reward_slots, rewards_fund)?;
+            ensure!(reward_amount >= T::MinReward::get(), Error::<T>::RewardBelowMinimum);
+            let reward_remainder = if survey.distribute_remainder {
+                rewards_fund.saturating_sub(reward_amount.saturating_mul(reward_slots))
+            } else {
+                0u32.into()
+            };
+
+            Ok(Survey {
+                reward_amount: Some(reward_amount),
+                reward_remainder,
+                ..extended_survey
+            })
+        }
+
+        /// Take from the owner of `survey` the funds for the slots `extended_survey` adds at the
+        /// same reward, returning it with its escrow increased.
+        fn fund_extension(
+            survey: &Survey<T>,
+            extended_survey: Survey<T>,
+            reward_amount: BalanceOf<T>,
+        ) -> Result<Survey<T>, DispatchError> {
+            let added_slots =
+                Self::reward_slots(&extended_survey)?.saturating_sub(Self::reward_slots(survey)?);
+            let added_participants =
+                extended_survey.participants_limit.saturating_sub(survey.participants_limit);
+            let fund_amount = arithmetic::mul_reward(reward_amount, added_slots)?
+                .checked_add(&arithmetic::mul_reward(survey.referral_reward, added_participants)?)
+                .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+
+            // Check that owner has enough balance for funding
+            let new_owner_balance = Self::debited_balance(
+                &survey.owner_id,
+                fund_amount,
+                Error::<T>::NotEnoughBalanceForFunding,
+            )?;
+
+            // Take the bonus of every added participant
+            let mut bonus_escrow = survey.bonus_escrow;
+            if let Some((asset_id, bonus_amount)) = survey.bonus {
+                let added: u128 = added_participants.saturated_into();
+                let added_bonus = bonus_amount
+                    .checked_mul(&added.saturated_into())
+                    .ok_or(Error::<T>::NotEnoughBonusForFunding)?;
+                <T::BonusAssets as fungibles::Mutate<AccountId<T>>>::burn_from(
+                    asset_id,
+                    &survey.owner_id,
+                    added_bonus,
+                    Precision::Exact,
+                    Fortitude::Polite,
+                )
+                .map_err(|_| Error::<T>::NotEnoughBonusForFunding)?;
+                bonus_escrow = bonus_escrow
+                    .checked_add(&added_bonus)
+                    .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+            }
+
+            Self::reserve_total_escrow(fund_amount)?;
+            let _ = <T::NativeBalance as fungible::Mutate<AccountId<T>>>::set_balance(
+                &survey.owner_id,
+                new_owner_balance,
+            );
+
+            Ok(Survey {
+                funded_amount: Some(
+                    survey
+                        .funded_amount
+                        .unwrap_or_default()
+                        .checked_add(&fund_amount)
+                        .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?,
+                ),
+                escrow_remaining: survey
+                    .escrow_remaining
+                    .checked_add(&fund_amount)
+                    .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?,
+                bonus_escrow,
+                ..extended_survey
+            })
+        }
+
+        /// Check that paying `reward_amount` for every one of `reward_slots` is covered by `fund_amount`.
+        ///
+        /// This always holds with a reward computed by floor division, but protects funding modes
+        /// computing the reward differently.
+        pub(crate) fn ensure_rewards_covered(
+            reward_amount: BalanceOf<T>,
+            reward_slots: BalanceOf<T>,
+            fund_amount: BalanceOf<T>,
+        ) -> Result<(), Error<T>> {
+            let committed_rewards = arithmetic::mul_reward(reward_amount, reward_slots)?;
+            ensure!(committed_rewards <= fund_amount, Error::<T>::RewardExceedsFunding);
+            Ok(())
+        }
+
+        /// Whether an account is a manager of a survey.
+        pub fn is_manager(survey_id: T::SurveyId, who: &AccountId<T>) -> bool {
+            Managers::<T>::contains_key(survey_id, who)
+        }
+
+        /// Check that an account is the owner or one of the managers of a survey.
+        fn ensure_owner_or_manager(survey: &Survey<T>, who: &AccountId<T>) -> Result<(), Error<T>> {
+            ensure!(
+                survey.owner_id == *who || Self::is_manager(survey.survey_id, who),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+
+        /// Apply `f` to the survey `survey_id`, writing it back only if `f` succeeds.
+        pub(crate) fn mutate_survey<R>(
+            survey_id: T::SurveyId,
+            f: impl FnOnce(&mut Survey<T>) -> Result<R, Error<T>>,
+        ) -> Result<R, Error<T>> {
+            SurveysMap::<T>::try_mutate(survey_id, |maybe_survey| {
+                let survey = maybe_survey.as_mut().ok_or(Error::<T>::SurveyNotCreated)?;
+                f(survey)
+            })
+        }
+
+        /// Whether `who` is the all-zero account, which may represent a burn address.
+        fn is_zero_account(who: &AccountId<T>) -> bool {
+            AccountId::<T>::decode(&mut TrailingZeroInput::zeroes())
+                .map_or(false, |zero_account| *who == zero_account)
+        }
+
+        /// Deposit an event unless it is more verbose than `Config::EventVerbosity`.
+        ///
+        /// Suppressed events still take their `seq`, so indexers can tell from gaps in the
+        /// sequence of a survey that events were left out.
+        fn emit_event(event: Event<T>) {
+            if event.level() <= T::EventVerbosity::get() {
+                Self::deposit_event(event);
+            }
+        }
+
+        /// Sequence number of the next event of a survey, incrementing the stored one.
+        fn next_event_seq(survey_id: T::SurveyId) -> u64 {
+            EventSeq::<T>::mutate(survey_id, |seq| {
+                let current = *seq;
+                *seq = seq.saturating_add(1);
+                current
+            })
+        }
+
+        /// Registration index of the next participant of a survey, incrementing the stored count.
+        fn next_registration_index(survey_id: T::SurveyId) -> BalanceOf<T> {
+            RegistrationCount::<T>::mutate(survey_id, |count| {
+                let current = *count;
+                *count = count.saturating_add(1u32.into());
+                current
+            })
+        }
+
+        /// Take the reward of a participant back into the escrow of a survey and unregister the
+        /// participant, clearing any dispute of the reward.
+        ///
+        /// The `RewardFee` cut of the reward is taken back from `TreasuryAccount`, so that escrow
+        /// and `total_paid` are restored by the gross reward escrow paid.
+        ///
+        /// `ClawbackWindow` is only checked if `check_window` is set, disputes raised within it
+        /// being resolvable afterwards.
+        ///
+        /// Returns the amount returned to escrow.
+        fn do_clawback(
+            survey_id: T::SurveyId,
+            caller: &AccountId<T>,
+            participant_id: &ParticipantId<T>,
+            check_window: bool,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            Self::mutate_survey(survey_id, |survey| {
+                // Check that caller is owner
+                ensure!(survey.owner_id == *caller, Error::<T>::NotOwnerOfSurvey);
+
+                ensure!(survey.status != Status::Cancelled, Error::<T>::SurveyCancelled);
+
+                let RewardReceipt { amount, fee, block: rewarded_at } =
+                    Self::reward_receipt(survey_id, participant_id)
+                        .ok_or(Error::<T>::ParticipantNotRewarded)?;
+                ensure!(
+                    !check_window || Self::within_clawback_window(rewarded_at),
+                    Error::<T>::ClawbackWindowExpired
+                );
+
+                // Take the reward back from the participant and its fee back from the treasury,
+                // neither of which must be spent nor locked
+                Self::take_back(participant_id, amount)?;
+                Self::take_back(&T::TreasuryAccount::get(), fee)?;
+                let gross_amount =
+                    amount.checked_add(&fee).ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+
+                survey.escrow_remaining = survey
+                    .escrow_remaining
+                    .checked_add(&gross_amount)
+                    .ok_or(Error::<T>::DefensiveUnexpectedOverflow)?;
+                // Clawed back rewards return to escrow regardless of `MaxTotalEscrow`
+                TotalEscrow::<T>::mutate(|total| *total = total.saturating_add(gross_amount));
+                survey.total_paid = survey.total_paid.saturating_sub(gross_amount);
+                survey.rewarded_count = survey.rewarded_count.saturating_sub(1u32.into());
+                survey.number_participants =
+                    survey.number_participants.saturating_sub(1u32.into());
+
+                // Unregister the participant
+                RewardReceipts::<T>::remove(survey_id, participant_id);
+                ParticipantsRewarded::<T>::remove(survey_id, participant_id);
+                Self::remove_participant(survey_id, participant_id);
+                JoinedSurveys::<T>::remove(participant_id, survey_id);
+                ParticipantScores::<T>::remove(survey_id, participant_id);
+                ParticipantResponses::<T>::remove(survey_id, participant_id);
+                RegistrationIndex::<T>::remove(survey_id, participant_id);
+                DisputedRewards::<T>::remove(survey_id, participant_id);
+                LifetimeRewards::<T>::mutate(participant_id, |total| {
+                    *total = total.saturating_sub(amount)
+                });
+
+                Ok(gross_amount)
+            })
+        }
+
+        /// Whether a reward paid at block `rewarded_at` can still be clawed back or disputed.
+        fn within_clawback_window(rewarded_at: BlockNumberFor<T>) -> bool {
+            frame_system::Pallet::<T>::block_number()
+                <= rewarded_at.saturating_add(T::ClawbackWindow::get())
+        }
+
+        /// Queue a survey to be completed by `on_initialize` in the first block past `deadline`.
+        ///
+        /// Surveys expiring in a block already holding `MaxExpiriesPerBlock` surveys are not
+        /// queued and have to be swept with `sweep_expired`.
+        fn schedule_expiry(survey_id: T::SurveyId, deadline: BlockNumberFor<T>) {
+            let expiry = deadline.saturating_add(One::one());
+            let queued =
+                SurveyExpiries::<T>::mutate(expiry, |survey_ids| survey_ids.try_push(survey_id));
+            if queued.is_err() {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "expiry queue full: survey_id = {:?}, block = {:?}",
+                    survey_id, expiry
+                );
+            }
+        }
+
+        /// Complete the surveys queued to expire up to block `now`, spending at most `limit`.
+        ///
+        /// Surveys left once the limit is reached stay queued, the next blocks resuming from them.
+        ///
+        /// Returns the weight consumed.
+        pub(crate) fn process_expiries(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            // Reading and writing the cursor
+            let mut consumed = db_weight.reads_writes(1, 1);
+            if consumed.any_gt(limit) {
+                return Weight::zero();
+            }
+            // Taking and possibly writing back the queue of a block
+            let block_weight = db_weight.reads_writes(1, 1);
+            // Reading and writing a survey and its event sequence
+            let survey_weight = db_weight.reads_writes(2, 2);
+            // Weight of a block completing a single survey, above which the survey never fits
+            let lone_survey_weight = consumed.saturating_add(block_weight);
+
+            let mut block = ExpiryCursor::<T>::get().unwrap_or(now);
+            while block <= now {
+                if consumed.saturating_add(block_weight).any_gt(limit) {
+                    break;
+                }
+                consumed = consumed.saturating_add(block_weight);
+
+                let survey_ids = SurveyExpiries::<T>::take(block);
+                let mut processed = 0;
+                for survey_id in survey_ids.iter() {
+                    let survey = SurveysMap::<T>::get(survey_id);
+                    let weight = survey.as_ref().map_or(survey_weight, |survey| {
+                        survey_weight.saturating_add(Self::finalize_weight(survey))
+                    });
+                    if consumed.saturating_add(weight).any_gt(limit) {
+                        // A survey too heavy for any block is left to `sweep_expired` rather
+                        // than holding back the queue
+                        if !lone_survey_weight.saturating_add(weight).any_gt(limit) {
+                            break;
+                        }
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "process_expiries: survey too heavy to expire, survey_id = {:?}",
+                            survey_id
+                        );
+                        consumed = consumed.saturating_add(db_weight.reads(1));
+                        processed += 1;
+                        continue;
+                    }
+                    consumed = consumed.saturating_add(weight);
+                    if let Some(survey) = survey {
+                        Self::expire_survey(*survey_id, survey, now);
+                    }
+                    processed += 1;
+                }
+
+                if processed < survey_ids.len() {
+                    let remaining = survey_ids.into_inner().split_off(processed);
+                    SurveyExpiries::<T>::insert(block, BoundedVec::truncate_from(remaining));
+                    break;
+                }
+                block = block.saturating_add(One::one());
+            }
+            ExpiryCursor::<T>::put(block);
+
+            consumed
+        }
+
+        /// Weight of `clear_participants`, which reads and removes up to `MaxClear` participants
+        /// and their registration, score, response and joined survey entries.
+        pub(crate) fn clear_participants_weight() -> Weight {
+            let max_clear = u64::from(T::MaxClear::get());
+            T::DbWeight::get().reads_writes(
+                max_clear.saturating_add(4),
+                max_clear.saturating_mul(5).saturating_add(4),
+            )
+        }
+
+        /// Weight of `finalize_rewards`, which pays up to `MaxBatchSize` participants, on top of
+        /// completing the survey.
+        pub(crate) fn finalize_rewards_weight() -> Weight {
+            let max_batch = u64::from(T::MaxBatchSize::get());
+            T::DbWeight::get().reads_writes(
+                max_batch.saturating_mul(12).saturating_add(11),
+                max_batch.saturating_mul(12).saturating_add(10),
+            )
+        }
+
+        /// Weight of `archive_survey`, which removes up to `MaxClear` participants with their
+        /// entries and up to `MaxClear` other entries, on top of archiving the survey.
+        pub(crate) fn archive_survey_weight() -> Weight {
+            let max_clear = u64::from(T::MaxClear::get());
+            T::DbWeight::get().reads_writes(
+                max_clear.saturating_mul(2).saturating_add(10),
+                max_clear.saturating_mul(9).saturating_add(12),
+            )
+        }
+
+        /// Weight of completing `survey` through `do_finalize`, on top of reading and writing it:
+        /// storing its post-hoc reward and escrow total, and crediting each of its participants
+        /// their share of the yield.
+        fn finalize_weight(survey: &Survey<T>) -> Weight {
+            let participants: u64 = survey.number_participants.saturated_into();
+            let operations = participants.saturating_add(2);
+            T::DbWeight::get().reads_writes(operations, operations)
+        }
+
+        /// Complete a survey whose deadline has passed at block `now`, unless it was already
+        /// completed, cancelled or its deadline moved.
+        ///
+        /// A survey whose completion fails is left to `sweep_expired`.
+        fn expire_survey(survey_id: T::SurveyId, mut survey: Survey<T>, now: BlockNumberFor<T>) {
+            if matches!(survey.status, Status::Completed | Status::Cancelled)
+                || !Self::is_expired(&survey, now)
+            {
+                return;
+            }
+
+            let completed = frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                Self::do_finalize(&mut survey, None)?;
+                SurveysMap::<T>::insert(survey_id, survey);
+                Ok(())
+            });
+            if let Err(e) = completed {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "expire_survey: completion failed: {:?}, survey_id = {:?}",
+                    e, survey_id
+                );
+                return;
+            }
+            T::OnSurveyLifecycle::on_completed(survey_id);
+
+            Self::emit_event(Event::SurveyAutoCompleted {
+                survey_id,
+                seq: Self::next_event_seq(survey_id),
+            });
+        }
+
+        /// Check that the pallet is not paused.
+        fn ensure_not_paused() -> Result<(), Error<T>> {
+            ensure!(!Self::is_paused(), Error::<T>::PalletPaused);
+            Ok(())
+        }
+
+        /// Check the invariants of the pallet storage.
+        ///
+        /// Every rewarded participant of a survey is one of its registered participants.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state() -> Result<(), DispatchError> {
+            for (survey_id, participant_id, _) in ParticipantsRewarded::<T>::iter() {
+                ensure!(
+                    Self::is_participant(survey_id, participant_id),
+                    "rewarded participant is not registered"
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Log a defensive error along with the survey and account it happened for.
+        ///
+        /// Defensive errors are never supposed to happen, hence this panics in tests.
+        fn defensive_error(error: Error<T>, survey_id: T::SurveyId, who: &AccountId<T>) -> Error<T> {
+            #[cfg(test)]
+            panic!(
+                "defensive error happened: {:?}, survey_id = {:?}, account = {:?}",
+                error, survey_id, who
+            );
+
+            log::error!(
+                target: LOG_TARGET,
+                "defensive error happened: {:?}, survey_id = {:?}, account = {:?}",
+                error, survey_id, who
+            );
+            error
+        }
+
+        /// Account which funded a survey, if the survey exists and is funded.
+        pub fn survey_funder(survey_id: T::SurveyId) -> Option<FunderId<T>> {
+            SurveysMap::<T>::get(survey_id).and_then(|survey| survey.funder_id)
+        }
+
+        /// Ids of the surveys with the given status.
+        /// Private surveys are only returned if `include_private` is set.
+        pub fn surveys_by_status(status: Status, include_private: bool) -> Vec<T::SurveyId> {
+            SurveysMap::<T>::iter_values()
+                .filter(|survey| survey.status == status && (include_private || survey.is_public))
+                .map(|survey| survey.survey_id)
+                .collect()
+        }
+
+        /// Ids of every survey of an owner, including private ones.
+        pub fn surveys_of_owner(owner_id: OwnerId<T>) -> Vec<T::SurveyId> {
+            SurveysMap::<T>::iter_values()
+                .filter(|survey| survey.owner_id == owner_id)
+                .map(|survey| survey.survey_id)
+                .collect()
+        }
+
+        /// Ids of every survey a participant is registered to.
+        pub fn surveys_joined_by(participant_id: ParticipantId<T>) -> Vec<T::SurveyId> {
+            JoinedSurveys::<T>::iter_key_prefix(participant_id).collect()
+        }
+
+        /// Surveys a participant is registered to but not rewarded yet, with the reward it is owed
+        /// net of `RewardFee`.
+        ///
+        /// Cancelled surveys, which never pay, and surveys whose reward is not known yet, such as
+        /// post-hoc surveys not finalized, are left out.
+        pub fn pending_claims(participant_id: ParticipantId<T>) -> Vec<(T::SurveyId, BalanceOf<T>)> {
+            JoinedSurveys::<T>::iter_key_prefix(&participant_id)
+                .filter(|survey_id| {
+                    !Self::is_participant_already_rewarded(*survey_id, participant_id.clone())
+                })
+                .filter_map(|survey_id| {
+                    let survey = Self::get_survey(survey_id)?;
+                    if survey.status == Status::Cancelled {
+                        return None;
+                    }
+                    let reward_amount = Self::participant_reward(&survey, &participant_id).ok()?;
+                    let fee = T::RewardFee::get() * reward_amount;
+                    Some((survey_id, reward_amount.saturating_sub(fee)))
+                })
+                .collect()
+        }
+
+        /// Number of decimals of the currency rewards are paid in.
+        pub fn reward_currency_decimals() -> u8 {
+            T::Decimals::get()
+        }
+
+        /// Whole and fractional parts of an amount of the reward currency, per `Decimals`.
+        /// Amounts are entirely fractional if a whole unit does not fit in the balance type.
+        pub fn format_balance(amount: BalanceOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
+            let unit = (0..T::Decimals::get()).try_fold(BalanceOf::<T>::one(), |unit, _| {
+                unit.checked_mul(&10u32.into())
+            });
+            match unit {
+                Some(unit) => (amount / unit, amount % unit),
+                None => (Zero::zero(), amount),
+            }
+        }
+
+        /// Up to `limit` participants of a survey who have been rewarded.
+        ///
+        /// Participants are returned in storage order, starting after `start` if set. Passing the
+        /// last returned participant as `start` resumes the listing.
+        pub fn list_rewarded(
+            survey_id: T::SurveyId,
+            start: Option<ParticipantId<T>>,
+            limit: u32,
+        ) -> Vec<ParticipantId<T>> {
+            let rewarded = match start {
+                Some(start) => ParticipantsRewarded::<T>::iter_prefix_from(
+                    survey_id,
+                    ParticipantsRewarded::<T>::hashed_key_for(survey_id, start),
+                ),
+                None => ParticipantsRewarded::<T>::iter_prefix(survey_id),
+            };
+
+            rewarded
+                .filter(|(_, is_rewarded)| *is_rewarded)
+                .map(|(participant_id, _)| participant_id)
+                .take(limit as usize)
+                .collect()
+        }
+
+        /// Registered participants of a survey who have not been rewarded yet, in the order
+        /// `distribute_rewards` pays them.
+        ///
+        /// Surveys distributed at random are ordered by hashing every participant with the
+        /// randomness drawn for the survey, so the order is stable for a given random seed.
+        pub fn reward_queue(survey_id: T::SurveyId) -> Vec<ParticipantId<T>> {
+            let Some(survey) = Self::get_survey(survey_id) else {
+                return Vec::new();
+            };
+
+            let mut queue: Vec<(BalanceOf<T>, ParticipantId<T>)> =
+                Self::participants_of(survey_id)
+                    .into_iter()
+                    .filter(|participant_id| {
+                        !Self::is_participant_already_rewarded(survey_id, participant_id.clone())
+                    })
+                    .map(|participant_id| {
+                        let index = Self::registration_index(survey_id, participant_id.clone())
+                            .unwrap_or_default();
+                        (index, participant_id)
+                    })
+                    .collect();
+            queue.sort_by_key(|(index, _)| *index);
+
+            if survey.distribution == DistributionOrder::Random {
+                let (seed, _) = T::Randomness::random(&survey_id.encode());
+                queue.sort_by_cached_key(|(_, participant_id)| {
+                    <T::Hashing as sp_runtime::traits::Hash>::hash_of(&(seed, participant_id))
+                });
+            }
+
+            queue.into_iter().map(|(_, participant_id)| participant_id).collect()
+        }
+
+        /// Number of registered participants of a survey who have not been rewarded yet, and the
+        /// total of their rewards.
+        ///
+        /// Nothing is written to storage. Rewards are not known before a post-hoc survey is
+        /// finalized, in which case the total is 0.
+        pub fn reward_all_preview(survey_id: T::SurveyId) -> (u32, BalanceOf<T>) {
+            let Some(survey) = Self::get_survey(survey_id) else {
+                return (0, 0u32.into());
+            };
+
+            Self::participants_of(survey_id)
+                .into_iter()
+                .filter(|participant_id| {
+                    !Self::is_participant_already_rewarded(survey_id, participant_id.clone())
+                })
+                .fold((0, 0u32.into()), |(count, total), participant_id| {
+                    let reward_amount =
+                        Self::participant_reward(&survey, &participant_id).unwrap_or_default();
+                    (count.saturating_add(1), total.saturating_add(reward_amount))
+                })
+        }
+
+        /// Reward of every participant of a survey funded with `fund_amount` for
+        /// `participants_limit` participants, 0 for a zero limit.
+        ///
+        /// Meant for previews only: funding validates its inputs and computes the reward itself.
+        pub fn reward_amount_saturating(
+            fund_amount: BalanceOf<T>,
+            participants_limit: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            fund_amount.checked_div(&participants_limit).unwrap_or_default()
+        }
+
+        /// Participant counts and escrow of a survey, read from its counters.
+        /// `None` if the survey does not exist.
+        pub fn reward_breakdown(survey_id: T::SurveyId) -> Option<RewardBreakdown<BalanceOf<T>>> {
+            let survey = Self::get_survey(survey_id)?;
+            Some(RewardBreakdown {
+                registered: survey.number_participants,
+                rewarded: survey.rewarded_count,
+                pending: survey.number_participants.saturating_sub(survey.rewarded_count),
+                escrow_remaining: survey.escrow_remaining,
+            })
+        }
+
+        /// Share of the funding of a survey paid out as rewards so far.
+        /// `None` if the survey does not exist or is not funded.
+        pub fn reward_utilization(survey_id: T::SurveyId) -> Option<Perbill> {
+            let survey = Self::get_survey(survey_id)?;
+            let funded_amount = survey.funded_amount.filter(|amount| !amount.is_zero())?;
+            let distributed =
+                survey.reward_amount.unwrap_or_default().saturating_mul(survey.rewarded_count);
+            Some(Perbill::from_rational(distributed, funded_amount))
+        }
+
+        /// Escrow of a survey left once the rewards owed to its registered but not yet rewarded
+        /// participants are set aside. `None` if the survey does not exist.
+        pub fn escrow_surplus(survey_id: T::SurveyId) -> Option<BalanceOf<T>> {
+            let survey = Self::get_survey(survey_id)?;
+            Some(survey.escrow_remaining.saturating_sub(Self::owed_escrow(&survey)))
+        }
+
+        /// Number of blocks left until the deadline of a survey, 0 once it has passed.
+        /// `None` if the survey does not exist or has no deadline.
+        pub fn blocks_until_deadline(survey_id: T::SurveyId) -> Option<BlockNumberFor<T>> {
+            let deadline = Self::get_survey(survey_id)?.deadline?;
+            Some(deadline.saturating_sub(frame_system::Pallet::<T>::block_number()))
+        }
+
+        /// Whether the deadline of a survey has passed at block `now`.
+        /// A survey without deadline never expires.
+        pub fn is_expired(survey: &Survey<T>, now: BlockNumberFor<T>) -> bool {
+            matches!(survey.deadline, Some(deadline) if deadline < now)
+        }
+    }
 }
@@ -0,0 +1,193 @@
+//! Storage migrations of the survey pallet.
+
+/// Migration of surveys stored before the pallet had a storage version.
+pub mod v1 {
+    use crate::pallet::{
+        AccountId, Config, CreationFlow, DistributionOrder, JoinedSurveys, NextSurveyId, Pallet,
+        ParticipantSets, Participants, ParticipantsRewarded, RegistrationCount, RegistrationIndex,
+        RewardAmounts, RewardRounding, Status, Survey, SurveyCount, SurveysMap, TotalEscrow,
+        LOG_TARGET,
+    };
+    use codec::{Decode, Encode};
+    use frame_support::{
+        log,
+        pallet_prelude::*,
+        traits::{fungible, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    };
+    use sp_runtime::traits::{One, Saturating};
+    use sp_std::vec::Vec;
+
+    type BalanceOf<T> =
+        <<T as Config>::NativeBalance as fungible::Inspect<AccountId<T>>>::Balance;
+
+    /// Survey as stored at storage version 0.
+    #[derive(Encode, Decode)]
+    pub struct OldSurvey<T: Config> {
+        pub survey_id: T::SurveyId,
+        pub owner_id: AccountId<T>,
+        pub participants_limit: BalanceOf<T>,
+        pub number_participants: BalanceOf<T>,
+        pub is_funded: bool,
+        pub funded_amount: Option<BalanceOf<T>>,
+        pub reward_amount: Option<BalanceOf<T>>,
+        pub status: Status,
+    }
+
+    /// Translate every survey to the current layout and fill the storage tracking surveys and
+    /// participants since storage version 0.
+    ///
+    /// Escrows are rebuilt from the funding of each survey less the rewards already paid, version
+    /// 0 having no fees, referrals or reclaims, and surveys being funded by their owner.
+    /// Participants are given registration indexes in storage order, version 0 not recording the
+    /// order they registered in.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 0 {
+                log::info!(
+                    target: LOG_TARGET,
+                    "MigrateToV1 skipped, storage is already past version 0"
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            let mut reads: u64 = 1;
+            let mut writes: u64 = 1;
+            let mut total_escrow: BalanceOf<T> = 0u32.into();
+            let mut next_survey_id = NextSurveyId::<T>::get();
+
+            SurveysMap::<T>::translate::<OldSurvey<T>, _>(|survey_id, old| {
+                let rewarded: u32 =
+                    ParticipantsRewarded::<T>::iter_prefix(survey_id).count() as u32;
+                reads = reads.saturating_add(u64::from(rewarded).saturating_add(1));
+                writes = writes.saturating_add(1);
+
+                let rewarded_count: BalanceOf<T> = rewarded.into();
+                let total_paid = old
+                    .reward_amount
+                    .map(|reward_amount| reward_amount.saturating_mul(rewarded_count))
+                    .unwrap_or_else(|| 0u32.into());
+                let escrow_remaining = match (old.is_funded, old.funded_amount) {
+                    (true, Some(funded_amount)) => funded_amount.saturating_sub(total_paid),
+                    _ => 0u32.into(),
+                };
+                if let Some(reward_amount) = old.reward_amount {
+                    RewardAmounts::<T>::insert(survey_id, reward_amount);
+                    writes = writes.saturating_add(1);
+                }
+                total_escrow = total_escrow.saturating_add(escrow_remaining);
+                if survey_id >= next_survey_id {
+                    next_survey_id = survey_id.saturating_add(One::one());
+                }
+
+                Some(Survey {
+                    survey_id: old.survey_id,
+                    funder_id: old.is_funded.then(|| old.owner_id.clone()),
+                    owner_id: old.owner_id,
+                    participants_limit: old.participants_limit,
+                    number_participants: old.number_participants,
+                    rewarded_count,
+                    is_funded: old.is_funded,
+                    funded_amount: old.funded_amount,
+                    reward_amount: old.reward_amount,
+                    status: old.status,
+                    deadline: None,
+                    vesting_blocks: None,
+                    early_bird_count: 0u32.into(),
+                    early_bird_multiplier: 1,
+                    reclaimable_surplus: 0u32.into(),
+                    is_public: true,
+                    funded_at: None,
+                    completed_at: None,
+                    status_changed_by: None,
+                    total_paid,
+                    deposit: 0u32.into(),
+                    bonus: None,
+                    bonus_escrow: 0u32.into(),
+                    escrow_remaining,
+                    post_hoc_rewards: false,
+                    referral_reward: 0u32.into(),
+                    min_participants: 0u32.into(),
+                    distribute_remainder: false,
+                    reward_remainder: 0u32.into(),
+                    distribution: DistributionOrder::Fifo,
+                    recompute_reward_on_extend: false,
+                    rounding: RewardRounding::Floor,
+                    creation_flow: CreationFlow::Separate,
+                })
+            });
+
+            let survey_count = SurveysMap::<T>::iter_keys().count() as u64;
+            reads = reads.saturating_add(survey_count);
+            SurveyCount::<T>::put(survey_count);
+            NextSurveyId::<T>::put(next_survey_id);
+            TotalEscrow::<T>::put(total_escrow);
+            writes = writes.saturating_add(3);
+
+            // Version 0 kept every participant in `Participants`
+            let participants: Vec<(T::SurveyId, AccountId<T>)> =
+                Participants::<T>::iter_keys().collect();
+            reads = reads.saturating_add(participants.len() as u64);
+            for (survey_id, participant_id) in participants {
+                JoinedSurveys::<T>::insert(&participant_id, survey_id, ());
+                let index = RegistrationCount::<T>::mutate(survey_id, |count| {
+                    let current = *count;
+                    *count = count.saturating_add(1u32.into());
+                    current
+                });
+                RegistrationIndex::<T>::insert(survey_id, participant_id.clone(), index);
+                reads = reads.saturating_add(1);
+                writes = writes.saturating_add(3);
+
+                // Participants which do not fit `MaxDenseParticipants` are left in `Participants`
+                if T::DenseParticipants::get() {
+                    let moved = ParticipantSets::<T>::try_mutate(survey_id, |set| {
+                        set.try_insert(participant_id.clone())
+                    });
+                    if moved.is_ok() {
+                        Participants::<T>::remove(survey_id, &participant_id);
+                    } else {
+                        log::error!(
+                            target: LOG_TARGET,
+                            "MigrateToV1 could not move a participant of survey {:?} to ParticipantSets",
+                            survey_id
+                        );
+                    }
+                    reads = reads.saturating_add(1);
+                    writes = writes.saturating_add(2);
+                }
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            log::info!(
+                target: LOG_TARGET,
+                "MigrateToV1 migrated {} surveys to storage version 1",
+                survey_count
+            );
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let survey_count = SurveysMap::<T>::iter_keys().count() as u64;
+            Ok(survey_count.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let survey_count = u64::decode(&mut &state[..])
+                .map_err(|_| "MigrateToV1 pre_upgrade state cannot be decoded")?;
+            ensure!(
+                SurveysMap::<T>::iter_values().count() as u64 == survey_count,
+                "MigrateToV1 lost surveys"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "MigrateToV1 did not set storage version 1"
+            );
+            Ok(())
+        }
+    }
+}
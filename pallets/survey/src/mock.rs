@@ -1,11 +1,21 @@
 use crate as pallet_survey;
 use codec::{Decode, Encode};
-use frame_support::traits::{ConstBool, ConstU128, ConstU16, ConstU32, ConstU64};
+use core::cell::RefCell;
+use frame_support::{
+    parameter_types,
+    traits::{
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8,
+        Randomness, VestingSchedule, WithdrawReasons,
+    },
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_runtime::DispatchResult;
+use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::H256;
 use sp_runtime::testing::UintAuthorityId as AuthorityId;
 use sp_runtime::{
-    traits::{BlakeTwo256, Convert, ConvertBack, IdentityLookup},
-    BuildStorage,
+    traits::{BlakeTwo256, Convert, ConvertBack, ConvertInto, IdentityLookup},
+    BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -23,6 +33,8 @@ frame_support::construct_runtime!(
     {
         System: frame_system,
         Balances: pallet_balances,
+        Vesting: pallet_vesting,
+        Assets: pallet_assets,
         PalletSurvey: pallet_survey,
     }
 );
@@ -31,7 +43,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = frame_support::traits::Everything;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type RuntimeOrigin = RuntimeOrigin;
     type RuntimeCall = RuntimeCall;
     type Nonce = u64;
@@ -57,7 +69,7 @@ impl pallet_balances::Config for Test {
     type Balance = Balance;
     type DustRemoval = ();
     type RuntimeEvent = RuntimeEvent;
-    type ExistentialDeposit = ConstU128<1>;
+    type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
     type MaxLocks = ConstU32<10>;
@@ -69,9 +81,179 @@ impl pallet_balances::Config for Test {
     type MaxFreezes = ConstU32<10>;
 }
 
+parameter_types! {
+    pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+        WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = ConstU128<1>;
+    type WeightInfo = ();
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+/// Vests rewards linearly with `pallet_vesting`, starting at the current block, unless
+/// `FailVesting` is set.
+pub struct VestingRewards;
+impl pallet_survey::RewardVesting<AccountId, Balance, u64> for VestingRewards {
+    fn vest_reward(who: &AccountId, amount: Balance, vesting_blocks: u64) -> DispatchResult {
+        if FailVesting::get() {
+            return Err(sp_runtime::DispatchError::Other("vesting failed"));
+        }
+        let per_block = (amount / Balance::from(vesting_blocks)).max(1);
+        <Vesting as VestingSchedule<AccountId>>::add_vesting_schedule(
+            who,
+            amount,
+            per_block,
+            System::block_number(),
+        )
+    }
+}
+
+/// Number of times each lifecycle hook was called.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LifecycleCalls {
+    pub created: u32,
+    pub funded: u32,
+    pub completed: u32,
+}
+
+thread_local! {
+    pub static LIFECYCLE_CALLS: RefCell<LifecycleCalls> = RefCell::new(LifecycleCalls::default());
+}
+
+pub fn lifecycle_calls() -> LifecycleCalls {
+    LIFECYCLE_CALLS.with(|calls| *calls.borrow())
+}
+
+pub struct CountingLifecycleHooks;
+impl pallet_survey::SurveyLifecycleHooks<AccountId, SurveyId> for CountingLifecycleHooks {
+    fn on_created(_: SurveyId, _: &AccountId) {
+        LIFECYCLE_CALLS.with(|calls| calls.borrow_mut().created += 1);
+    }
+
+    fn on_funded(_: SurveyId, _: &AccountId) {
+        LIFECYCLE_CALLS.with(|calls| calls.borrow_mut().funded += 1);
+    }
+
+    fn on_completed(_: SurveyId) {
+        LIFECYCLE_CALLS.with(|calls| calls.borrow_mut().completed += 1);
+    }
+}
+
+/// Returns `RandomSeed` for every subject.
+pub struct SeededRandomness;
+impl Randomness<H256, u64> for SeededRandomness {
+    fn random(_: &[u8]) -> (H256, u64) {
+        (RandomSeed::get(), System::block_number())
+    }
+}
+
+/// Accrues `AccruedYield` to the escrow of every survey.
+pub struct FixedYield;
+impl pallet_survey::EscrowYieldSource<SurveyId, Balance> for FixedYield {
+    fn take_accrued_yield(_: SurveyId) -> Balance {
+        AccruedYield::get()
+    }
+}
+
+/// Every account is eligible but `IneligibleAccount`.
+pub struct RejectingEligibility;
+impl pallet_survey::EligibilityCheck<AccountId, SurveyId> for RejectingEligibility {
+    fn is_eligible(who: &AccountId, _: SurveyId) -> bool {
+        Some(*who) != IneligibleAccount::get()
+    }
+}
+
+pub const TREASURY: AccountId = 100;
+
+parameter_types! {
+    pub const CancellationSlash: Perbill = Perbill::from_percent(10);
+    pub static ExistentialDeposit: Balance = 1;
+    pub static ForceCreateAccounts: bool = false;
+    pub static DenseParticipants: bool = false;
+    pub static RewardDelay: u64 = 0;
+    pub static CreationFee: Balance = 0;
+    pub static MaxRewardPerParticipant: Balance = Balance::MAX;
+    pub static UnscoredFullReward: bool = true;
+    pub static FeeDestination: Option<AccountId> = None;
+    pub static FailVesting: bool = false;
+    pub static MinReward: Balance = 1;
+    pub static MaxTotalEscrow: Balance = Balance::MAX;
+    pub static RewardFee: Perbill = Perbill::zero();
+    pub static RandomSeed: H256 = H256::zero();
+    pub static AccruedYield: Balance = 0;
+    pub static IneligibleAccount: Option<AccountId> = None;
+    pub static EventVerbosity: pallet_survey::EventLevel = pallet_survey::EventLevel::Full;
+    pub static MaxExpiryWeight: Weight = Weight::MAX;
+}
+
 impl pallet_survey::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type NativeBalance = Balances;
+    type SurveyId = SurveyId;
+    type TreasuryAccount = ConstU64<TREASURY>;
+    type SweepReward = ConstU128<5>;
+    type MaxSweep = ConstU32<10>;
+    type MaxBatchSize = ConstU32<10>;
+    type MaxExpiryWeight = MaxExpiryWeight;
+    type MaxExpiriesPerBlock = ConstU32<100>;
+    type MaxAnnouncementLen = ConstU32<16>;
+    type MaxClear = ConstU32<3>;
+    type MinFunding = ConstU128<100>;
+    type MinReward = MinReward;
+    type MaxTotalEscrow = MaxTotalEscrow;
+    type SurveyDeposit = ConstU128<10>;
+    type CancellationSlash = CancellationSlash;
+    type RewardFee = RewardFee;
+    type SlashDestination = ConstU64<TREASURY>;
+    type MaxScore = ConstU8<100>;
+    type UnscoredFullReward = UnscoredFullReward;
+    type MaxRewardPerParticipant = MaxRewardPerParticipant;
+    type CreationFee = CreationFee;
+    type FeeDestination = FeeDestination;
+    type ForceCreateAccounts = ForceCreateAccounts;
+    type DenseParticipants = DenseParticipants;
+    type MaxDenseParticipants = ConstU32<16>;
+    type Decimals = ConstU8<12>;
+    type RewardDelay = RewardDelay;
+    type ClawbackWindow = ConstU64<10>;
+    type BonusAssets = Assets;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type VestingHandler = VestingRewards;
+    type OnSurveyLifecycle = CountingLifecycleHooks;
+    type YieldSource = FixedYield;
+    type ParticipantEligibility = RejectingEligibility;
+    type FundingSource = pallet_survey::SpendableBalance<Test>;
+    type EventVerbosity = EventVerbosity;
+    type Randomness = SeededRandomness;
 }
 
 // Build genesis storage according to the mock runtime.
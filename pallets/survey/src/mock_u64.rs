@@ -0,0 +1,163 @@
+//! Mock runtime identifying surveys with `u64` rather than `u128`, checking that the pallet does
+//! not rely on a particular `Config::SurveyId`.
+
+use crate as pallet_survey;
+use frame_support::{
+    parameter_types,
+    traits::{
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8,
+        Randomness,
+    },
+    weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage, Perbill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+pub type AccountId = u64;
+pub type SurveyId = u64;
+
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
+        PalletSurvey: pallet_survey,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ConstU32<10>;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ConstU32<10>;
+    type MaxFreezes = ConstU32<10>;
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+/// Same output for every subject, ordering rewards deterministically.
+pub struct ZeroRandomness;
+impl Randomness<H256, u64> for ZeroRandomness {
+    fn random(_: &[u8]) -> (H256, u64) {
+        (H256::zero(), System::block_number())
+    }
+}
+
+pub const TREASURY: AccountId = 100;
+
+parameter_types! {
+    pub const CancellationSlash: Perbill = Perbill::from_percent(10);
+    pub const MaxExpiryWeight: Weight = Weight::MAX;
+    pub const EventVerbosity: pallet_survey::EventLevel = pallet_survey::EventLevel::Full;
+}
+
+impl pallet_survey::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type NativeBalance = Balances;
+    type SurveyId = SurveyId;
+    type TreasuryAccount = ConstU64<TREASURY>;
+    type SweepReward = ConstU128<5>;
+    type MaxSweep = ConstU32<10>;
+    type MaxBatchSize = ConstU32<10>;
+    type MaxExpiryWeight = MaxExpiryWeight;
+    type MaxExpiriesPerBlock = ConstU32<100>;
+    type MaxAnnouncementLen = ConstU32<16>;
+    type MaxClear = ConstU32<3>;
+    type MinFunding = ConstU128<100>;
+    type MinReward = ConstU128<1>;
+    type MaxTotalEscrow = ConstU128<{ Balance::MAX }>;
+    type SurveyDeposit = ConstU128<10>;
+    type CancellationSlash = CancellationSlash;
+    type RewardFee = ();
+    type SlashDestination = ConstU64<TREASURY>;
+    type MaxScore = ConstU8<100>;
+    type UnscoredFullReward = ConstBool<true>;
+    type MaxRewardPerParticipant = ConstU128<{ Balance::MAX }>;
+    type CreationFee = ConstU128<0>;
+    type FeeDestination = ();
+    type ForceCreateAccounts = ConstBool<false>;
+    type DenseParticipants = ConstBool<false>;
+    type MaxDenseParticipants = ConstU32<16>;
+    type Decimals = ConstU8<12>;
+    type RewardDelay = ConstU64<0>;
+    type ClawbackWindow = ConstU64<10>;
+    type BonusAssets = Assets;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type VestingHandler = ();
+    type OnSurveyLifecycle = ();
+    type YieldSource = ();
+    type ParticipantEligibility = ();
+    type FundingSource = pallet_survey::SpendableBalance<Test>;
+    type EventVerbosity = EventVerbosity;
+    type Randomness = ZeroRandomness;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap()
+        .into()
+}
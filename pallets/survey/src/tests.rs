@@ -1,13 +1,16 @@
-use crate::{mock::*, AccountId, Config, Event, Status, Survey};
+use crate::{
+    mock::*, AccountId, ArchivedSurvey, Config, Event, RewardBreakdown, Status, Survey,
+    SurveyTemplate,
+};
 use codec::Encode;
 use frame_support::{
     assert_noop, assert_ok,
     traits::{
         fungible::{self},
-        OnFinalize, OnInitialize,
+        fungibles, Get, OnFinalize, OnInitialize, VestingSchedule,
     },
 };
-use sp_runtime::BoundedVec;
+use sp_runtime::{BoundedVec, Perbill};
 
 // Utils
 
@@ -20,6 +23,9 @@ fn initialize_state() -> (crate::mock::AccountId, crate::mock::AccountId) {
             AccountId<Test>,
         >>::mint_into(&i, 1000000000));
     }
+    assert_ok!(<<Test as Config>::NativeBalance as fungible::Mutate<
+        AccountId<Test>,
+    >>::mint_into(&TREASURY, 1000000000));
     (1, 2)
 }
 
@@ -66,6 +72,7 @@ fn create_new_survey_success() {
             events.pop(),
             Some(Event::SurveyCreated {
                 survey_id,
+                seq: 0,
                 owner_id: survey_owner
             })
         );
@@ -123,6 +130,7 @@ fn fund_survey_success() {
             events.pop(),
             Some(Event::SurveyFunded {
                 survey_id,
+                seq: 1,
                 funder_id: survey_owner,
                 funded_amount: 1000000
             })
@@ -130,6 +138,31 @@ fn fund_survey_success() {
     });
 }
 
+#[test]
+fn fund_survey_records_funder() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_eq!(PalletSurvey::survey_funder(survey_id), None);
+
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10000
+        ));
+
+        assert_eq!(PalletSurvey::survey_funder(survey_id), Some(survey_owner));
+        assert_eq!(get_survey(survey_id).funder_id, Some(survey_owner));
+    });
+}
+
 #[test]
 fn fund_survey_gives_expected_reward_amount_10000_for_1000() {
     new_test_ext().execute_with(|| {
@@ -269,6 +302,71 @@ fn fund_survey_fails_survey_not_enough_balance() {
     });
 }
 
+#[test]
+fn fund_survey_success_funding_at_minimum() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 10;
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        let fund_amount = <Test as Config>::MinFunding::get();
+
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            fund_amount
+        ));
+        assert_eq!(get_survey(survey_id).funded_amount, Some(fund_amount));
+    });
+}
+
+#[test]
+fn fund_survey_fails_funding_below_minimum() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 10;
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        let fund_amount = <Test as Config>::MinFunding::get() - 1;
+
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, fund_amount),
+            crate::Error::<Test>::FundingBelowMinimum
+        );
+    });
+}
+
+#[test]
+fn ensure_rewards_covered_rejects_rewards_exceeding_funding() {
+    new_test_ext().execute_with(|| {
+        // Rewards of a floor division funding are covered
+        assert_ok!(PalletSurvey::ensure_rewards_covered(10, 1000, 10999));
+        assert_ok!(PalletSurvey::ensure_rewards_covered(10, 1000, 10000));
+
+        // Rewards rounded up by a crafted funding mode are not
+        assert!(matches!(
+            PalletSurvey::ensure_rewards_covered(11, 1000, 10999),
+            Err(crate::Error::<Test>::RewardExceedsFunding)
+        ));
+
+        // Overflowing committed rewards are rejected
+        assert!(matches!(
+            PalletSurvey::ensure_rewards_covered(u128::MAX, 2, u128::MAX),
+            Err(crate::Error::<Test>::DefensiveUnexpectedOverflow)
+        ));
+    });
+}
+
 // create_and_fud_survey
 #[test]
 fn create_and_fund_survey_success() {
@@ -287,10 +385,20 @@ fn create_and_fund_survey_success() {
 
         // Test events
         let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::SurveyCreatedAndFunded {
+                survey_id,
+                seq: 2,
+                owner_id: survey_owner,
+                funded_amount: 1000000
+            })
+        );
         assert_eq!(
             events.pop(),
             Some(Event::SurveyFunded {
                 survey_id,
+                seq: 1,
                 funder_id: survey_owner,
                 funded_amount: 1000000
             })
@@ -299,12 +407,59 @@ fn create_and_fund_survey_success() {
             events.pop(),
             Some(Event::SurveyCreated {
                 survey_id,
+                seq: 0,
                 owner_id: survey_owner
             })
         );
     });
 }
 
+#[test]
+fn create_and_fund_survey_fails_for_survey_of_another_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+        let fund_amount = 1000000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        // Another account can neither recreate nor fund the survey through the combined call
+        assert_noop!(
+            PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(other_owner),
+                survey_id,
+                participants_limit,
+                fund_amount
+            ),
+            crate::Error::<Test>::SurveyAlreadyCreated
+        );
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.owner_id, survey_owner);
+        assert!(!survey.is_funded);
+    });
+}
+
+#[test]
+fn create_and_fund_survey_fails_unsigned_origin() {
+    new_test_ext().execute_with(|| {
+        let (_survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        // Origin is verified once, before any survey is created
+        assert_noop!(
+            PalletSurvey::create_and_fund_survey(RuntimeOrigin::none(), survey_id, 1000, 1000000),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert!(PalletSurvey::get_survey(survey_id).is_none());
+    });
+}
+
 // register_participant
 #[test]
 fn register_participant_success() {
@@ -335,6 +490,7 @@ fn register_participant_success() {
             events.pop(),
             Some(Event::NewParticipantRegistered {
                 survey_id,
+                seq: 3,
                 participant_id
             })
         );
@@ -384,6 +540,32 @@ fn register_participant_fails_survey_not_funded() {
     });
 }
 
+#[test]
+fn register_participant_fails_zero_account() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 0),
+            crate::Error::<Test>::InvalidParticipant
+        );
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+    });
+}
+
 #[test]
 fn register_participant_fails_participant_already_registered() {
     new_test_ext().execute_with(|| {
@@ -437,7 +619,7 @@ fn register_participant_fails_not_owner() {
                 survey_id,
                 participant_id
             ),
-            crate::Error::<Test>::NotOwnerOfSurvey
+            crate::Error::<Test>::NotAuthorized
         );
     });
 }
@@ -502,7 +684,7 @@ fn register_participant_fails_survey_is_not_active() {
                 survey_id,
                 participant_id
             ),
-            crate::Error::<Test>::SurveyIsNotActive
+            crate::Error::<Test>::SurveyPaused
         );
 
         assert_ok!(PalletSurvey::set_survey_status(
@@ -517,8 +699,33 @@ fn register_participant_fails_survey_is_not_active() {
                 survey_id,
                 participant_id
             ),
-            crate::Error::<Test>::SurveyIsNotActive
+            crate::Error::<Test>::SurveyCompleted
+        );
+    });
+}
+
+#[test]
+fn register_participant_fails_for_ineligible_account() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        IneligibleAccount::set(Some(3));
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 3),
+            crate::Error::<Test>::ParticipantNotEligible
         );
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
     });
 }
 
@@ -581,7 +788,7 @@ fn set_survey_status_fails_not_owner() {
                 survey_id,
                 Status::Paused,
             ),
-            crate::Error::<Test>::NotOwnerOfSurvey
+            crate::Error::<Test>::NotAuthorized
         );
     });
 }
@@ -624,6 +831,7 @@ fn reward_participant_success() {
             events.pop(),
             Some(Event::RewardClaimed {
                 survey_id,
+                seq: 4,
                 participant_id,
                 reward_amount: reward_amount_expected
             })
@@ -723,7 +931,7 @@ fn reward_participant_fails_already_rewarded() {
         ));
 
         assert_ok!(PalletSurvey::reward_participant(
-            RuntimeOrigin::signed(participant_id),
+            RuntimeOrigin::signed(survey_owner),
             survey_id,
             participant_id
         ));
@@ -794,7 +1002,6464 @@ fn reward_participant_fails_not_owner() {
                 survey_id,
                 participant_id
             ),
-            crate::Error::<Test>::NotOwnerOfSurvey
+            crate::Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+// lifetime_rewards
+#[test]
+fn lifetime_rewards_accumulate_across_surveys() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let first_survey_id: SurveyId = 0;
+        let second_survey_id: SurveyId = 1;
+
+        // Reward amount of 10 for first survey
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            first_survey_id,
+            1000,
+            10000
+        ));
+
+        // Reward amount of 100 for second survey
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            second_survey_id,
+            100,
+            10000
+        ));
+
+        assert_eq!(PalletSurvey::lifetime_rewards(participant_id), 0);
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            first_survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            first_survey_id,
+            participant_id
+        ));
+
+        assert_eq!(PalletSurvey::lifetime_rewards(participant_id), 10);
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            second_survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            second_survey_id,
+            participant_id
+        ));
+
+        assert_eq!(PalletSurvey::lifetime_rewards(participant_id), 110);
+    });
+}
+
+// set_survey_deadline
+#[test]
+fn set_survey_deadline_success() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_ok!(PalletSurvey::set_survey_deadline(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+
+        let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::SurveyDeadlineSet {
+                survey_id,
+                seq: 1,
+                deadline: 10
+            })
+        );
+        assert_eq!(get_survey(survey_id).deadline, Some(10));
+    });
+}
+
+#[test]
+fn set_survey_deadline_fails_deadline_in_the_past() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_noop!(
+            PalletSurvey::set_survey_deadline(RuntimeOrigin::signed(survey_owner), survey_id, 1),
+            crate::Error::<Test>::DeadlineInThePast
+        );
+    });
+}
+
+// sweep_expired
+#[test]
+fn sweep_expired_completes_only_expired_surveys_and_rewards_caller() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, sweeper) = initialize_state();
+        let participants_limit: ParticipantLimitType = 1000;
+
+        // Surveys 0 and 1 expire at block 5, survey 2 at block 50, survey 3 never
+        for survey_id in 0..4 {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participants_limit
+            ));
+        }
+        for (survey_id, deadline) in [(0, 5), (1, 5), (2, 50)] {
+            assert_ok!(PalletSurvey::set_survey_deadline(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                deadline
+            ));
+        }
+
+        System::set_block_number(10);
+
+        let sweeper_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&sweeper);
+        let treasury_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY);
+
+        // Survey 4 does not exist and is ignored
+        assert_ok!(PalletSurvey::sweep_expired(
+            RuntimeOrigin::signed(sweeper),
+            BoundedVec::try_from(vec![0, 1, 2, 3, 4]).unwrap()
+        ));
+
+        assert_eq!(get_survey(0).status, Status::Completed);
+        assert_eq!(get_survey(1).status, Status::Completed);
+        assert_eq!(get_survey(2).status, Status::Active);
+        assert_eq!(get_survey(3).status, Status::Active);
+
+        let reward_amount = 2 * <Test as Config>::SweepReward::get();
+        let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::ExpiredSurveysSwept {
+                sweeper,
+                swept_count: 2,
+                reward_amount
+            })
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&sweeper),
+            sweeper_balance_before + reward_amount
         );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY),
+            treasury_balance_before - reward_amount
+        );
+
+        // Sweeping again does not pay anything as surveys are already completed
+        assert_ok!(PalletSurvey::sweep_expired(
+            RuntimeOrigin::signed(sweeper),
+            BoundedVec::try_from(vec![0, 1]).unwrap()
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&sweeper),
+            sweeper_balance_before + reward_amount
+        );
+    });
+}
+
+// propose_new_owner / accept_ownership
+#[test]
+fn pending_owner_is_set_until_ownership_accepted() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, new_owner) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_eq!(PalletSurvey::pending_owner(survey_id), None);
+
+        assert_ok!(PalletSurvey::propose_new_owner(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            new_owner
+        ));
+
+        assert_eq!(PalletSurvey::pending_owner(survey_id), Some(new_owner));
+        assert_eq!(get_survey(survey_id).owner_id, survey_owner);
+
+        assert_ok!(PalletSurvey::accept_ownership(
+            RuntimeOrigin::signed(new_owner),
+            survey_id
+        ));
+
+        let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::SurveyOwnershipTransferred {
+                survey_id,
+                seq: 2,
+                old_owner_id: survey_owner,
+                new_owner_id: new_owner
+            })
+        );
+        assert_eq!(PalletSurvey::pending_owner(survey_id), None);
+        assert_eq!(get_survey(survey_id).owner_id, new_owner);
+    });
+}
+
+#[test]
+fn accept_ownership_fails_not_proposed_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, new_owner) = initialize_state();
+        let other_account = 3;
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_noop!(
+            PalletSurvey::accept_ownership(RuntimeOrigin::signed(new_owner), survey_id),
+            crate::Error::<Test>::NotProposedOwner
+        );
+
+        assert_ok!(PalletSurvey::propose_new_owner(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            new_owner
+        ));
+
+        assert_noop!(
+            PalletSurvey::accept_ownership(RuntimeOrigin::signed(other_account), survey_id),
+            crate::Error::<Test>::NotProposedOwner
+        );
+        assert_noop!(
+            PalletSurvey::propose_new_owner(
+                RuntimeOrigin::signed(new_owner),
+                survey_id,
+                new_owner
+            ),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// defensive errors
+#[test]
+#[should_panic(expected = "defensive error happened: DefensiveErrorWhenDividing, survey_id = 0")]
+fn fund_survey_defensive_error_reports_survey_context() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant) = initialize_state();
+        let survey_id: SurveyId = 0;
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1));
+        // A zero participants limit, rejected on creation, makes the reward computation divide
+        // by zero
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().participants_limit = 0;
+        });
+
+        let _ = PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000);
+    });
+}
+
+// survey templates
+#[test]
+fn create_from_template_instantiates_identical_surveys() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let template = SurveyTemplate::<Test> {
+            participants_limit: 500,
+            duration: Some(20),
+        };
+
+        assert_ok!(PalletSurvey::create_template(
+            RuntimeOrigin::signed(survey_owner),
+            template.clone()
+        ));
+
+        let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::TemplateCreated {
+                owner_id: survey_owner,
+                template_id: 0
+            })
+        );
+        assert_eq!(PalletSurvey::get_template(survey_owner, 0), Some(template));
+
+        assert_ok!(PalletSurvey::create_from_template(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            0
+        ));
+        assert_ok!(PalletSurvey::create_from_template(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            0
+        ));
+
+        for survey_id in [0, 1] {
+            let survey = get_survey(survey_id);
+            assert_eq!(survey.owner_id, survey_owner);
+            assert_eq!(survey.participants_limit, 500);
+            assert_eq!(survey.deadline, Some(21));
+            assert_eq!(survey.status, Status::Active);
+        }
+    });
+}
+
+#[test]
+fn create_from_template_fails_template_of_another_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+        let template = SurveyTemplate::<Test> {
+            participants_limit: 500,
+            duration: None,
+        };
+
+        assert_ok!(PalletSurvey::create_template(
+            RuntimeOrigin::signed(survey_owner),
+            template
+        ));
+
+        assert_noop!(
+            PalletSurvey::create_from_template(RuntimeOrigin::signed(other_owner), 0, 0),
+            crate::Error::<Test>::TemplateNotFound
+        );
+    });
+}
+
+#[test]
+fn delete_template_success() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let template = SurveyTemplate::<Test> {
+            participants_limit: 500,
+            duration: None,
+        };
+
+        assert_ok!(PalletSurvey::create_template(
+            RuntimeOrigin::signed(survey_owner),
+            template
+        ));
+        assert_ok!(PalletSurvey::delete_template(
+            RuntimeOrigin::signed(survey_owner),
+            0
+        ));
+
+        assert_eq!(PalletSurvey::get_template(survey_owner, 0), None);
+        assert_noop!(
+            PalletSurvey::create_from_template(RuntimeOrigin::signed(survey_owner), 0, 0),
+            crate::Error::<Test>::TemplateNotFound
+        );
+        assert_noop!(
+            PalletSurvey::delete_template(RuntimeOrigin::signed(survey_owner), 0),
+            crate::Error::<Test>::TemplateNotFound
+        );
+    });
+}
+
+// set_vesting_blocks
+#[test]
+fn reward_participant_vests_reward_over_vesting_blocks() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+        let fund_amount = 100000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+        assert_ok!(PalletSurvey::set_vesting_blocks(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Some(10)
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            fund_amount
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Reward is credited but fully locked
+        let reward_amount = 100;
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before + reward_amount
+        );
+        assert_eq!(Vesting::vesting_balance(&participant_id), Some(100));
+
+        // Half of the vesting period unlocks half of the reward
+        System::set_block_number(6);
+        assert_eq!(Vesting::vesting_balance(&participant_id), Some(50));
+
+        // Whole reward is unlocked at the end of the vesting period
+        System::set_block_number(11);
+        assert_eq!(Vesting::vesting_balance(&participant_id), Some(0));
+    });
+}
+
+#[test]
+fn set_vesting_blocks_fails_survey_already_funded() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            100000
+        ));
+
+        assert_noop!(
+            PalletSurvey::set_vesting_blocks(RuntimeOrigin::signed(survey_owner), survey_id, Some(10)),
+            crate::Error::<Test>::SurveyAlreadyFunded
+        );
+    });
+}
+
+// set_pallet_paused
+#[test]
+fn set_survey_status_fails_survey_not_created_before_pallet_paused() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let missing_survey_id: SurveyId = 1;
+        let participants_limit: ParticipantLimitType = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+
+        assert_ok!(PalletSurvey::set_pallet_paused(RuntimeOrigin::root(), true));
+        assert!(PalletSurvey::is_paused());
+
+        // Existence is checked before the pallet pause
+        assert_noop!(
+            PalletSurvey::set_survey_status(
+                RuntimeOrigin::signed(survey_owner),
+                missing_survey_id,
+                Status::Paused
+            ),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+
+        // Pallet pause is checked before ownership
+        assert_noop!(
+            PalletSurvey::set_survey_status(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                Status::Paused
+            ),
+            crate::Error::<Test>::PalletPaused
+        );
+        assert_noop!(
+            PalletSurvey::set_survey_status(RuntimeOrigin::signed(3), survey_id, Status::Paused),
+            crate::Error::<Test>::PalletPaused
+        );
+
+        assert_ok!(PalletSurvey::set_pallet_paused(RuntimeOrigin::root(), false));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Paused
+        ));
+    });
+}
+
+#[test]
+fn set_pallet_paused_fails_not_admin() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+
+        assert_noop!(
+            PalletSurvey::set_pallet_paused(RuntimeOrigin::signed(survey_owner), true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+// set_early_bird
+#[test]
+fn reward_participant_pays_early_birds_multiplied_reward() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 10;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+        // First two participants earn a triple reward
+        assert_ok!(PalletSurvey::set_early_bird(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2,
+            3
+        ));
+        // 10 rewards + 2 bonus rewards for each of the 2 early birds
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1400
+        ));
+        assert_eq!(get_survey(survey_id).reward_amount, Some(100));
+
+        for (participant_id, expected_reward) in [(2, 300), (3, 300), (4, 100)] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+
+            let balance_before =
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+                balance_before + expected_reward
+            );
+        }
+    });
+}
+
+#[test]
+fn fund_survey_fails_funding_insufficient_for_early_bird() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 10;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+        assert_ok!(PalletSurvey::set_early_bird(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            5,
+            100
+        ));
+
+        // 10 + 5 * 99 rewards cannot be covered
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 200),
+            crate::Error::<Test>::FundingInsufficientForEarlyBird
+        );
+    });
+}
+
+// reduce_participants_limit
+#[test]
+fn reduce_participants_limit_frees_surplus_of_freed_slots() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        // Reward amount of 10
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            10000
+        ));
+        for participant_id in 2..7 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        assert_ok!(PalletSurvey::reduce_participants_limit(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+
+        let mut events = get_events();
+        assert_eq!(
+            events.pop(),
+            Some(Event::ParticipantsLimitReduced {
+                survey_id,
+                seq: 8,
+                new_limit: 10,
+                reclaimable_surplus: 9900
+            })
+        );
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.participants_limit, 10);
+        assert_eq!(survey.reward_amount, Some(10));
+        assert_eq!(survey.reclaimable_surplus, 9900);
+
+        let owner_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_ok!(PalletSurvey::reclaim_surplus(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before + 9900
+        );
+        assert_eq!(get_survey(survey_id).reclaimable_surplus, 0);
+        assert_noop!(
+            PalletSurvey::reclaim_surplus(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::NoSurplusToReclaim
+        );
+    });
+}
+
+#[test]
+fn reduce_participants_limit_fails_limit_below_current_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            10000
+        ));
+        for participant_id in 2..7 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        assert_noop!(
+            PalletSurvey::reduce_participants_limit(RuntimeOrigin::signed(survey_owner), survey_id, 4),
+            crate::Error::<Test>::LimitBelowCurrentParticipants
+        );
+        assert_noop!(
+            PalletSurvey::reduce_participants_limit(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                1000
+            ),
+            crate::Error::<Test>::LimitNotReduced
+        );
+    });
+}
+
+// set_survey_visibility
+#[test]
+fn private_survey_is_excluded_from_discovery_but_returned_to_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+        let public_survey_id: SurveyId = 0;
+        let private_survey_id: SurveyId = 1;
+        let other_survey_id: SurveyId = 2;
+
+        for survey_id in [public_survey_id, private_survey_id] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                1000
+            ));
+        }
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(other_owner),
+            other_survey_id,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_survey_visibility(
+            RuntimeOrigin::signed(survey_owner),
+            private_survey_id,
+            false
+        ));
+        assert!(!get_survey(private_survey_id).is_public);
+
+        let mut public_surveys = PalletSurvey::surveys_by_status(Status::Active, false);
+        public_surveys.sort();
+        assert_eq!(public_surveys, vec![public_survey_id, other_survey_id]);
+
+        let mut all_surveys = PalletSurvey::surveys_by_status(Status::Active, true);
+        all_surveys.sort();
+        assert_eq!(
+            all_surveys,
+            vec![public_survey_id, private_survey_id, other_survey_id]
+        );
+
+        let mut owner_surveys = PalletSurvey::surveys_of_owner(survey_owner);
+        owner_surveys.sort();
+        assert_eq!(owner_surveys, vec![public_survey_id, private_survey_id]);
+    });
+}
+
+#[test]
+fn set_survey_visibility_fails_not_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::set_survey_visibility(RuntimeOrigin::signed(other_owner), survey_id, false),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// add_manager / remove_manager
+#[test]
+fn manager_can_register_and_reward_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let manager_id = 3;
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            10000
+        ));
+        assert_ok!(PalletSurvey::add_manager(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            manager_id
+        ));
+        assert!(PalletSurvey::is_manager(survey_id, &manager_id));
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(manager_id),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(manager_id),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(manager_id),
+            survey_id,
+            Status::Paused
+        ));
+
+        // Managers cannot manage other managers
+        assert_noop!(
+            PalletSurvey::add_manager(RuntimeOrigin::signed(manager_id), survey_id, 4),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+#[test]
+fn removed_manager_is_not_authorized() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let manager_id = 3;
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            10000
+        ));
+        assert_ok!(PalletSurvey::add_manager(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            manager_id
+        ));
+        assert_ok!(PalletSurvey::remove_manager(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            manager_id
+        ));
+        assert!(!PalletSurvey::is_manager(survey_id, &manager_id));
+
+        assert_noop!(
+            PalletSurvey::register_participant(
+                RuntimeOrigin::signed(manager_id),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            PalletSurvey::remove_manager(RuntimeOrigin::signed(survey_owner), survey_id, manager_id),
+            crate::Error::<Test>::ManagerNotFound
+        );
+    });
+}
+
+// batch_create_surveys
+#[test]
+fn batch_create_surveys_creates_every_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        assert_ok!(PalletSurvey::batch_create_surveys(
+            RuntimeOrigin::signed(survey_owner),
+            BoundedVec::try_from(vec![(0, 10), (1, 20), (2, 30)]).unwrap()
+        ));
+
+        for (survey_id, participants_limit) in [(0, 10), (1, 20), (2, 30)] {
+            let survey = get_survey(survey_id);
+            assert_eq!(survey.owner_id, survey_owner);
+            assert_eq!(survey.participants_limit, participants_limit);
+            assert!(!survey.is_funded);
+        }
+
+        let created = get_events()
+            .into_iter()
+            .filter(|event| matches!(event, Event::SurveyCreated { .. }))
+            .count();
+        assert_eq!(created, 3);
+    });
+}
+
+#[test]
+fn batch_create_surveys_fails_on_duplicate_id() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        assert_noop!(
+            PalletSurvey::batch_create_surveys(
+                RuntimeOrigin::signed(survey_owner),
+                BoundedVec::try_from(vec![(0, 10), (1, 20), (0, 30)]).unwrap()
+            ),
+            crate::Error::<Test>::SurveyAlreadyCreated
+        );
+
+        assert!(PalletSurvey::get_survey(0).is_none());
+        assert!(PalletSurvey::get_survey(1).is_none());
+    });
+}
+
+// finalize_rewards
+#[test]
+fn finalize_rewards_splits_funding_between_actual_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants: Vec<crate::mock::AccountId> = (10..20).collect();
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        assert_eq!(get_survey(survey_id).reward_amount, None);
+
+        for participant_id in participants.iter() {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                *participant_id
+            ));
+        }
+
+        // Participants cannot be rewarded one by one
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participants[0]
+            ),
+            crate::Error::<Test>::RewardsDeferred
+        );
+
+        assert_ok!(PalletSurvey::finalize_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        for participant_id in participants.iter() {
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id),
+                100
+            );
+            assert!(PalletSurvey::is_participant_already_rewarded(
+                survey_id,
+                *participant_id
+            ));
+        }
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.reward_amount, Some(100));
+        assert_eq!(survey.status, Status::Completed);
+
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardsFinalized {
+                survey_id,
+                seq: 24,
+                reward_amount: 100,
+                rewarded_count: 10,
+            })
+        );
+
+        assert_noop!(
+            PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::RewardsAlreadyFinalized
+        );
+    });
+}
+
+#[test]
+fn finalize_rewards_fails_without_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::NoParticipantsToReward
+        );
+    });
+}
+
+#[test]
+fn finalize_rewards_fails_not_post_hoc() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::NotPostHocSurvey
+        );
+    });
+}
+
+#[test]
+fn finalize_rewards_resumes_across_calls() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants: Vec<crate::mock::AccountId> = (10..22).collect();
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1200
+        ));
+        for participant_id in participants.iter() {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                *participant_id
+            ));
+        }
+
+        // The first call fixes the reward and pays `MaxBatchSize` participants
+        assert_ok!(PalletSurvey::finalize_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.reward_amount, Some(100));
+        assert_eq!(survey.status, Status::Completed);
+        assert_eq!(survey.rewarded_count, 10);
+        assert!(PalletSurvey::finalization_cursor(survey_id).is_some());
+        assert!(matches!(
+            get_events().last(),
+            Some(Event::RewardsFinalized { reward_amount: 100, rewarded_count: 10, .. })
+        ));
+
+        // The second call pays the participants left
+        assert_ok!(PalletSurvey::finalize_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert_eq!(get_survey(survey_id).rewarded_count, 12);
+        assert!(PalletSurvey::finalization_cursor(survey_id).is_none());
+        assert!(matches!(
+            get_events().last(),
+            Some(Event::RewardsFinalized { reward_amount: 100, rewarded_count: 2, .. })
+        ));
+        for participant_id in participants.iter() {
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id),
+                100
+            );
+        }
+        assert_eq!(get_survey(survey_id).escrow_remaining, 0);
+
+        assert_noop!(
+            PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::RewardsAlreadyFinalized
+        );
+
+        // The call is charged for the participants it may pay
+        let weight = frame_support::dispatch::GetDispatchInfo::get_dispatch_info(
+            &crate::Call::<Test>::finalize_rewards { survey_id },
+        )
+        .weight;
+        assert_eq!(weight, PalletSurvey::finalize_rewards_weight());
+    });
+}
+
+// RewardAmounts
+#[test]
+fn cached_reward_amount_matches_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 100));
+        assert_eq!(PalletSurvey::cached_reward_amount(0), None);
+
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1000));
+        assert_eq!(PalletSurvey::cached_reward_amount(0), get_survey(0).reward_amount);
+        assert_eq!(PalletSurvey::cached_reward_amount(0), Some(10));
+
+        // Post-hoc surveys only cache their reward once finalized
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 1, 100));
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 1000));
+        assert_eq!(PalletSurvey::cached_reward_amount(1), None);
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), 1));
+        assert_eq!(PalletSurvey::cached_reward_amount(1), get_survey(1).reward_amount);
+        assert_eq!(PalletSurvey::cached_reward_amount(1), Some(1000));
+    });
+}
+
+// list_rewarded
+#[test]
+fn list_rewarded_returns_only_rewarded_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in 2..7 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        for participant_id in [2, 4, 6] {
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        let mut rewarded = PalletSurvey::list_rewarded(survey_id, None, 10);
+        rewarded.sort();
+        assert_eq!(rewarded, vec![2, 4, 6]);
+
+        // Pages resume after the last returned participant
+        let mut paged = Vec::new();
+        let mut start = None;
+        loop {
+            let page = PalletSurvey::list_rewarded(survey_id, start, 2);
+            if page.is_empty() {
+                break;
+            }
+            start = page.last().copied();
+            paged.extend(page);
+        }
+        paged.sort();
+        assert_eq!(paged, vec![2, 4, 6]);
+
+        assert!(PalletSurvey::list_rewarded(1, None, 10).is_empty());
+    });
+}
+
+// RewardFailedDefensive
+#[test]
+fn reward_fails_defensively_when_escrow_is_drained() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1000);
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Drain the escrow
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().escrow_remaining = 0
+        });
+
+        let balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+        // The failure is handled, so that the event is kept on-chain
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardFailedDefensive {
+                survey_id,
+                seq: 4,
+                participant_id,
+            })
+        );
+        assert!(!PalletSurvey::is_participant_already_rewarded(
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_before
+        );
+        assert_eq!(get_survey(survey_id).rewarded_count, 0);
+    });
+}
+
+#[test]
+fn reward_participant_decreases_escrow() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(get_survey(survey_id).escrow_remaining, 990);
+    });
+}
+
+// OnSurveyLifecycle
+#[test]
+fn lifecycle_hooks_fire_once_per_action() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_eq!(lifecycle_calls(), LifecycleCalls { created: 1, funded: 0, completed: 0 });
+
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        assert_eq!(lifecycle_calls(), LifecycleCalls { created: 1, funded: 1, completed: 0 });
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_eq!(lifecycle_calls(), LifecycleCalls { created: 1, funded: 1, completed: 1 });
+
+        // Completing an already completed survey does not fire the hook again
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_eq!(lifecycle_calls(), LifecycleCalls { created: 1, funded: 1, completed: 1 });
+    });
+}
+
+// cancel_survey
+#[test]
+fn cancel_survey_refunds_escrow_and_slashes_deposit() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let owner_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+        let treasury_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_eq!(get_survey(survey_id).deposit, 10);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before - 1010
+        );
+
+        assert_ok!(PalletSurvey::cancel_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        // Owner gets the escrow and `deposit * (1 - slash)` back
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before - 1
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY),
+            treasury_balance_before + 1
+        );
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.status, Status::Cancelled);
+        assert_eq!(survey.escrow_remaining, 0);
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyCancelled {
+                survey_id,
+                seq: 3,
+                refunded_amount: 1009,
+                slashed_amount: 1,
+            })
+        );
+
+        assert_noop!(
+            PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::SurveyCancelled
+        );
+    });
+}
+
+#[test]
+fn cancel_survey_fails_not_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+
+        assert_noop!(
+            PalletSurvey::cancel_survey(RuntimeOrigin::signed(participant_id), survey_id),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// register_participant_idempotent
+#[test]
+fn register_participant_idempotent_succeeds_twice_with_one_event() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+
+        for _ in 0..2 {
+            assert_ok!(PalletSurvey::register_participant_idempotent(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        let registered = get_events()
+            .into_iter()
+            .filter(|event| matches!(event, Event::NewParticipantRegistered { .. }))
+            .count();
+        assert_eq!(registered, 1);
+        assert_eq!(get_survey(survey_id).number_participants, 1);
+    });
+}
+
+#[test]
+fn register_participant_idempotent_fails_other_preconditions() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_noop!(
+            PalletSurvey::register_participant_idempotent(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant_idempotent(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::SurveyNotFunded
+        );
+    });
+}
+
+// reward_currency_decimals
+#[test]
+fn reward_currency_decimals_returns_configured_value() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(PalletSurvey::reward_currency_decimals(), 12);
+        assert_eq!(
+            PalletSurvey::reward_currency_decimals(),
+            <<Test as Config>::Decimals as Get<u8>>::get()
+        );
+    });
+}
+
+#[test]
+fn pallet_paused_blocks_funding_but_not_rewarding() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            100,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 1, 100));
+
+        assert_ok!(PalletSurvey::set_pallet_paused(RuntimeOrigin::root(), true));
+
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 2, 100),
+            crate::Error::<Test>::PalletPaused
+        );
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 1000),
+            crate::Error::<Test>::PalletPaused
+        );
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), 0, 3),
+            crate::Error::<Test>::PalletPaused
+        );
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert!(PalletSurvey::is_participant_already_rewarded(0, participant_id));
+    });
+}
+
+// EventSeq
+#[test]
+fn event_seq_increments_per_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 100));
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1000));
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 1, 100));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+
+        let seqs_of = |id: SurveyId| {
+            get_events()
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::SurveyCreated { survey_id, seq, .. } |
+                    Event::SurveyFunded { survey_id, seq, .. } |
+                    Event::NewParticipantRegistered { survey_id, seq, .. } |
+                    Event::RewardClaimed { survey_id, seq, .. }
+                        if survey_id == id =>
+                        Some(seq),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(seqs_of(0), vec![0, 1, 2, 3]);
+        assert_eq!(seqs_of(1), vec![0]);
+        assert_eq!(PalletSurvey::event_seq(0), 4);
+        assert_eq!(PalletSurvey::event_seq(1), 1);
+    });
+}
+
+// RewardSkippedBelowED
+#[test]
+fn reward_below_existential_deposit_is_skipped() {
+    new_test_ext().execute_with(|| {
+        ExistentialDeposit::set(50);
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participant_id = 10;
+
+        // Reward amount of 10
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardSkippedBelowED {
+                survey_id,
+                seq: 4,
+                participant_id,
+                reward_amount: 10,
+            })
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            0
+        );
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1000);
+    });
+}
+
+#[test]
+fn reward_below_existential_deposit_is_topped_up_when_forced() {
+    new_test_ext().execute_with(|| {
+        ExistentialDeposit::set(50);
+        ForceCreateAccounts::set(true);
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participant_id = 10;
+
+        // Reward amount of 10
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardClaimed {
+                survey_id,
+                seq: 4,
+                participant_id,
+                reward_amount: 50,
+            })
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            50
+        );
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 950);
+    });
+}
+
+// clear_participants
+#[test]
+fn clear_participants_resets_registrations() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        for participant_id in 2..7 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        // At most `MaxClear` participants are removed per call
+        assert_ok!(PalletSurvey::clear_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert_eq!(get_survey(survey_id).number_participants, 2);
+        assert_ok!(PalletSurvey::clear_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert_eq!(get_survey(survey_id).number_participants, 0);
+        assert!(matches!(
+            get_events().last(),
+            Some(Event::ParticipantsCleared { removed: 2, .. })
+        ));
+        for participant_id in 2..7 {
+            assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        }
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(get_survey(survey_id).number_participants, 1);
+        assert_eq!(PalletSurvey::registration_index(survey_id, participant_id), Some(0));
+
+        // The call is charged for the participants it may read and remove
+        let weight = frame_support::dispatch::GetDispatchInfo::get_dispatch_info(
+            &crate::Call::<Test>::clear_participants { survey_id },
+        )
+        .weight;
+        assert_eq!(weight, PalletSurvey::clear_participants_weight());
+        assert!(weight.ref_time() > 0);
+    });
+}
+
+#[test]
+fn clear_participants_fails_after_rewards() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(get_survey(survey_id).rewarded_count, 1);
+
+        assert_noop!(
+            PalletSurvey::clear_participants(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::CannotClearAfterRewards
+        );
+    });
+}
+
+// mutate_survey
+#[test]
+fn mutate_survey_persists_only_successful_mutations() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+
+        assert!(matches!(
+            PalletSurvey::mutate_survey(survey_id, |survey| {
+                survey.participants_limit = 50;
+                Ok(survey.participants_limit)
+            }),
+            Ok(50)
+        ));
+        assert_eq!(get_survey(survey_id).participants_limit, 50);
+
+        // Failed mutations are not written back
+        assert!(matches!(
+            PalletSurvey::mutate_survey(survey_id, |survey| -> Result<(), _> {
+                survey.participants_limit = 10;
+                Err(crate::Error::<Test>::LimitNotReduced)
+            }),
+            Err(crate::Error::<Test>::LimitNotReduced)
+        ));
+        assert_eq!(get_survey(survey_id).participants_limit, 50);
+
+        assert!(matches!(
+            PalletSurvey::mutate_survey(1, |_| Ok(())),
+            Err(crate::Error::<Test>::SurveyNotCreated)
+        ));
+    });
+}
+
+// set_survey_bonus
+fn create_bonus_asset(owner: crate::mock::AccountId, amount: u128) -> u32 {
+    let asset_id = 1;
+    assert_ok!(<Assets as fungibles::Create<crate::mock::AccountId>>::create(
+        asset_id, owner, true, 1
+    ));
+    assert_ok!(<Assets as fungibles::Mutate<crate::mock::AccountId>>::mint_into(
+        asset_id, &owner, amount
+    ));
+    asset_id
+}
+
+#[test]
+fn reward_participant_pays_bonus_asset() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let asset_id = create_bonus_asset(survey_owner, 1000);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+        assert_ok!(PalletSurvey::set_survey_bonus(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Some((asset_id, 100))
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyBonusSet {
+                survey_id,
+                seq: 1,
+                bonus: Some((asset_id, 100)),
+            })
+        );
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+
+        // Bonus for every participant is taken at funding
+        assert_eq!(get_survey(survey_id).bonus_escrow, 200);
+        assert_eq!(
+            <Assets as fungibles::Inspect<crate::mock::AccountId>>::balance(asset_id, &survey_owner),
+            800
+        );
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        let participant_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            participant_balance_before + 500
+        );
+        assert_eq!(
+            <Assets as fungibles::Inspect<crate::mock::AccountId>>::balance(asset_id, &participant_id),
+            100
+        );
+        assert_eq!(get_survey(survey_id).bonus_escrow, 100);
+    });
+}
+
+#[test]
+fn fund_survey_fails_not_enough_bonus() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let asset_id = create_bonus_asset(survey_owner, 150);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+        assert_ok!(PalletSurvey::set_survey_bonus(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Some((asset_id, 100))
+        ));
+
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::NotEnoughBonusForFunding
+        );
+    });
+}
+
+// arithmetic
+#[test]
+fn add_participant_count_fails_on_overflow() {
+    assert!(matches!(
+        crate::arithmetic::add_participant_count::<Test, u128>(u128::MAX - 1),
+        Ok(u128::MAX)
+    ));
+    assert!(matches!(
+        crate::arithmetic::add_participant_count::<Test, u128>(u128::MAX),
+        Err(crate::Error::<Test>::DefensiveUnexpectedOverflow)
+    ));
+}
+
+#[test]
+fn sub_escrow_fails_on_underflow() {
+    assert!(matches!(crate::arithmetic::sub_escrow::<Test, u128>(100, 100), Ok(0)));
+    assert!(matches!(
+        crate::arithmetic::sub_escrow::<Test, u128>(100, 101),
+        Err(crate::Error::<Test>::DefensiveNotEnoughFundsInSurveyForReward)
+    ));
+}
+
+#[test]
+fn mul_reward_fails_on_overflow() {
+    assert!(matches!(
+        crate::arithmetic::mul_reward::<Test, u128>(u128::MAX / 2, 2),
+        Ok(value) if value == u128::MAX - 1
+    ));
+    assert!(matches!(
+        crate::arithmetic::mul_reward::<Test, u128>(u128::MAX / 2 + 1, 2),
+        Err(crate::Error::<Test>::DefensiveUnexpectedOverflow)
+    ));
+}
+
+// register_with_referrer
+#[test]
+fn register_with_referrer_pays_referral_from_escrow() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, referrer) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participant_id = 3;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+        assert_ok!(PalletSurvey::set_referral_reward(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+
+        // Referral rewards are set aside before splitting the funding
+        assert_eq!(get_survey(survey_id).reward_amount, Some(400));
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            referrer
+        ));
+        let referrer_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&referrer);
+
+        assert_ok!(PalletSurvey::register_with_referrer(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            referrer
+        ));
+
+        assert!(PalletSurvey::is_participant(survey_id, participant_id));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&referrer),
+            referrer_balance_before + 100
+        );
+        assert_eq!(get_survey(survey_id).escrow_remaining, 900);
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::ReferralRewardPaid {
+                survey_id,
+                seq: 5,
+                referrer,
+                participant_id,
+                referral_reward: 100,
+            })
+        );
+    });
+}
+
+#[test]
+fn register_with_referrer_skips_zero_referral_reward() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, referrer) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participant_id = 3;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            referrer
+        ));
+        let referrer_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&referrer);
+
+        assert_ok!(PalletSurvey::register_with_referrer(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            referrer
+        ));
+
+        assert!(PalletSurvey::is_participant(survey_id, participant_id));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&referrer),
+            referrer_balance_before
+        );
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1000);
+        assert!(get_events()
+            .iter()
+            .all(|event| !matches!(event, Event::ReferralRewardPaid { .. })));
+        assert!(matches!(
+            get_events().last(),
+            Some(Event::NewParticipantRegistered { participant_id: 3, .. })
+        ));
+    });
+}
+
+#[test]
+fn register_with_referrer_fails_invalid_referrer() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+
+        // Self-referral
+        assert_noop!(
+            PalletSurvey::register_with_referrer(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id,
+                participant_id
+            ),
+            crate::Error::<Test>::InvalidReferrer
+        );
+
+        // Referrer not registered
+        assert_noop!(
+            PalletSurvey::register_with_referrer(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id,
+                3
+            ),
+            crate::Error::<Test>::InvalidReferrer
+        );
+    });
+}
+
+// reward_all_preview
+#[test]
+fn reward_all_preview_counts_unrewarded_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3,
+            3000
+        ));
+        for participant in [participant_id, 3, 4] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+        assert_eq!(PalletSurvey::reward_all_preview(survey_id), (3, 3000));
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let escrow_before = get_survey(survey_id).escrow_remaining;
+        assert_eq!(PalletSurvey::reward_all_preview(survey_id), (2, 2000));
+        // Nothing is paid by the preview
+        assert_eq!(get_survey(survey_id).escrow_remaining, escrow_before);
+        assert_eq!(PalletSurvey::reward_all_preview(1), (0, 0));
+    });
+}
+
+// RewardDelay
+#[test]
+fn reward_participant_fails_before_reward_delay() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        RewardDelay::set(5);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_eq!(get_survey(survey_id).funded_at, Some(1));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        System::set_block_number(5);
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::RewardTooEarly
+        );
+
+        System::set_block_number(6);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert!(PalletSurvey::is_participant_already_rewarded(
+            survey_id,
+            participant_id
+        ));
+    });
+}
+
+// batch_register_participants
+#[test]
+fn batch_register_participants_works() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::batch_register_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            BoundedVec::try_from(vec![participant_id, 3]).unwrap()
+        ));
+
+        assert!(PalletSurvey::is_participant(survey_id, participant_id));
+        assert!(PalletSurvey::is_participant(survey_id, 3));
+        assert_eq!(get_survey(survey_id).number_participants, 2);
+    });
+}
+
+#[test]
+fn batch_register_participants_fails_duplicate_in_batch() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::batch_register_participants(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                BoundedVec::try_from(vec![participant_id, 3, participant_id]).unwrap()
+            ),
+            crate::Error::<Test>::DuplicateInBatch
+        );
+
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        assert!(!PalletSurvey::is_participant(survey_id, 3));
+        assert_eq!(get_survey(survey_id).number_participants, 0);
+    });
+}
+
+// archive_survey
+#[test]
+fn archive_survey_keeps_summary_and_clears_storage() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        let owner_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_ok!(PalletSurvey::archive_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        // Hot storage is freed and the deposit returned
+        assert!(PalletSurvey::get_survey(survey_id).is_none());
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        assert!(!PalletSurvey::is_participant_already_rewarded(
+            survey_id,
+            participant_id
+        ));
+        assert!(PalletSurvey::cached_reward_amount(survey_id).is_none());
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before + 10
+        );
+
+        assert_eq!(
+            PalletSurvey::get_archived_survey(survey_id),
+            Some(ArchivedSurvey {
+                owner: survey_owner,
+                total_participants: 1,
+                total_paid: 1000,
+                completed_at: Some(1),
+            })
+        );
+
+        // Archived surveys are no longer operable
+        assert_noop!(
+            PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                3
+            ),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), survey_id, 10),
+            crate::Error::<Test>::SurveyAlreadyCreated
+        );
+    });
+}
+
+#[test]
+fn archive_survey_fails_not_settled() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::archive_survey(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::SurveyNotSettled
+        );
+
+        // Completed surveys still holding escrow are not settled either
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_noop!(
+            PalletSurvey::archive_survey(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::SurveyNotSettled
+        );
+    });
+}
+
+#[test]
+fn archive_survey_resumes_until_every_entry_is_removed() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_account) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            5,
+            500
+        ));
+        for participant_id in 3..8 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+
+        // The first call archives the survey and removes `MaxClear` participants
+        assert_ok!(PalletSurvey::archive_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert!(PalletSurvey::get_survey(survey_id).is_none());
+        assert!(PalletSurvey::get_archived_survey(survey_id).is_some());
+        assert!(PalletSurvey::is_archival_pending(survey_id));
+        assert_eq!(PalletSurvey::participants_of(survey_id).len(), 2);
+        assert!(!matches!(get_events().last(), Some(Event::SurveyArchived { .. })));
+
+        // The survey cannot be operated while its archival is pending
+        assert_noop!(
+            PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                8
+            ),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+        assert_noop!(
+            PalletSurvey::archive_survey(RuntimeOrigin::signed(other_account), survey_id),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+
+        assert_ok!(PalletSurvey::archive_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert!(!PalletSurvey::is_archival_pending(survey_id));
+        assert!(matches!(get_events().last(), Some(Event::SurveyArchived { .. })));
+        for participant_id in 3..8 {
+            assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+            assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+            assert!(PalletSurvey::reward_receipt(survey_id, participant_id).is_none());
+            assert!(PalletSurvey::registration_index(survey_id, participant_id).is_none());
+            assert!(!crate::JoinedSurveys::<Test>::contains_key(participant_id, survey_id));
+        }
+        assert_eq!(
+            PalletSurvey::get_archived_survey(survey_id).map(|archived| archived.total_paid),
+            Some(500)
+        );
+
+        // Nothing is left to archive
+        assert_noop!(
+            PalletSurvey::archive_survey(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+
+        // The call is charged for the entries it may remove
+        let weight = frame_support::dispatch::GetDispatchInfo::get_dispatch_info(
+            &crate::Call::<Test>::archive_survey { survey_id },
+        )
+        .weight;
+        assert_eq!(weight, PalletSurvey::archive_survey_weight());
+    });
+}
+
+// ensure_active
+#[test]
+fn reward_participant_allowed_on_paused_and_completed_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant in [participant_id, 3] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Paused
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+    });
+}
+
+#[test]
+fn reward_participant_fails_cancelled_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::cancel_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::SurveyCancelled
+        );
+    });
+}
+
+#[test]
+fn fund_survey_fails_survey_not_active() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Paused
+        ));
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::SurveyPaused
+        );
+
+        assert_ok!(PalletSurvey::cancel_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::SurveyCancelled
+        );
+    });
+}
+
+// CreationFee
+#[test]
+fn create_survey_sends_creation_fee_to_destination() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        CreationFee::set(50);
+        FeeDestination::set(Some(TREASURY));
+        let owner_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+        let treasury_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10
+        ));
+
+        // Deposit and fee leave the owner, only the fee reaching the destination
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before - 60
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY),
+            treasury_balance_before + 50
+        );
+    });
+}
+
+#[test]
+fn create_survey_burns_creation_fee_without_destination() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        CreationFee::set(50);
+        let issuance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::total_issuance();
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10
+        ));
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::total_issuance(),
+            issuance_before - 60
+        );
+    });
+}
+
+#[test]
+fn create_survey_fails_cannot_afford_creation_fee() {
+    new_test_ext().execute_with(|| {
+        initialize_state();
+        CreationFee::set(50);
+        let poor_owner = 20;
+        assert_ok!(<<Test as Config>::NativeBalance as fungible::Mutate<
+            AccountId<Test>,
+        >>::mint_into(&poor_owner, 40));
+
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(poor_owner), 0, 10),
+            crate::Error::<Test>::CannotAffordCreationFee
+        );
+    });
+}
+
+// blocks_until_deadline
+#[test]
+fn blocks_until_deadline_counts_down_to_zero() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_eq!(PalletSurvey::blocks_until_deadline(survey_id), None);
+
+        assert_ok!(PalletSurvey::set_survey_deadline(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_eq!(PalletSurvey::blocks_until_deadline(survey_id), Some(9));
+
+        System::set_block_number(15);
+        assert_eq!(PalletSurvey::blocks_until_deadline(survey_id), Some(0));
+
+        assert_eq!(PalletSurvey::blocks_until_deadline(1), None);
+    });
+}
+
+// create_survey_auto_id
+#[test]
+fn create_survey_auto_id_skips_manual_ids() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey_auto_id(
+            RuntimeOrigin::signed(survey_owner),
+            10
+        ));
+        assert_eq!(get_survey(0).owner_id, survey_owner);
+        assert_eq!(PalletSurvey::next_survey_id(), 1);
+
+        // Manual ids bump the counter past them
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            5,
+            10
+        ));
+        assert_eq!(PalletSurvey::next_survey_id(), 6);
+
+        assert_ok!(PalletSurvey::create_survey_auto_id(
+            RuntimeOrigin::signed(survey_owner),
+            10
+        ));
+        assert!(PalletSurvey::get_survey(6).is_some());
+        assert_eq!(PalletSurvey::next_survey_id(), 7);
+
+        // Lower manual ids leave the counter untouched
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            10
+        ));
+        assert_eq!(PalletSurvey::next_survey_id(), 7);
+    });
+}
+
+#[test]
+fn create_survey_auto_id_skips_ids_in_use() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        // Surveys created before the counter existed
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10
+        ));
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            10
+        ));
+        crate::NextSurveyId::<Test>::put(0);
+
+        assert_ok!(PalletSurvey::create_survey_auto_id(
+            RuntimeOrigin::signed(survey_owner),
+            10
+        ));
+        assert!(PalletSurvey::get_survey(2).is_some());
+        assert_eq!(PalletSurvey::next_survey_id(), 3);
+    });
+}
+
+// top_up_escrow
+#[test]
+fn top_up_escrow_recovers_drained_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Simulate an escrow drained by a faulty funding mode
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().escrow_remaining = 0;
+        });
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+
+        let owner_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+        assert_ok!(PalletSurvey::top_up_escrow(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance_before - 100
+        );
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::EscrowToppedUp {
+                survey_id,
+                seq: 5,
+                amount: 100,
+                escrow_remaining: 100,
+            })
+        );
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 0);
+    });
+}
+
+#[test]
+fn top_up_escrow_fails_not_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::top_up_escrow(RuntimeOrigin::signed(participant_id), survey_id, 100),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// withdraw_participation
+#[test]
+fn withdraw_participation_frees_slot() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 3),
+            crate::Error::<Test>::MaxNumberOfParticipantsReached
+        );
+
+        assert_ok!(PalletSurvey::withdraw_participation(
+            RuntimeOrigin::signed(participant_id),
+            survey_id
+        ));
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        assert_eq!(get_survey(survey_id).number_participants, 0);
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::ParticipationWithdrawn {
+                survey_id,
+                seq: 4,
+                participant_id,
+            })
+        );
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+    });
+}
+
+#[test]
+fn withdraw_participation_fails_already_rewarded() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::withdraw_participation(RuntimeOrigin::signed(participant_id), survey_id),
+            crate::Error::<Test>::ParticipantNotRegistered
+        );
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::withdraw_participation(RuntimeOrigin::signed(participant_id), survey_id),
+            crate::Error::<Test>::ParticipantAlreadyRewarded
+        );
+    });
+}
+
+// MaxRewardPerParticipant
+#[test]
+fn fund_survey_enforces_reward_cap() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        MaxRewardPerParticipant::set(100);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10,
+            1000
+        ));
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            10
+        ));
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 1010),
+            crate::Error::<Test>::RewardExceedsPolicyCap
+        );
+    });
+}
+
+#[test]
+fn set_reward_amount_enforces_reward_cap() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        MaxRewardPerParticipant::set(100);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            5,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_reward_amount(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            50
+        ));
+        assert_eq!(PalletSurvey::cached_reward_amount(survey_id), Some(50));
+
+        assert_noop!(
+            PalletSurvey::set_reward_amount(RuntimeOrigin::signed(survey_owner), survey_id, 150),
+            crate::Error::<Test>::RewardExceedsPolicyCap
+        );
+    });
+}
+
+#[test]
+fn reward_participant_enforces_reward_cap() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Policy tightened after funding
+        MaxRewardPerParticipant::set(99);
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::RewardExceedsPolicyCap
+        );
+
+        MaxRewardPerParticipant::set(100);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+    });
+}
+
+// batch_reward_silent
+#[test]
+fn batch_reward_silent_emits_only_summary_event() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::batch_register_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            BoundedVec::try_from(vec![participant_id, 3, 4]).unwrap()
+        ));
+        System::reset_events();
+
+        assert_ok!(PalletSurvey::batch_reward_silent(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            BoundedVec::try_from(vec![participant_id, 3, 4]).unwrap()
+        ));
+
+        assert_eq!(
+            get_events(),
+            vec![Event::BatchRewarded {
+                survey_id,
+                seq: 6,
+                count: 3,
+                total: 300,
+            }]
+        );
+        for participant in [participant_id, 3, 4] {
+            assert!(PalletSurvey::is_participant_already_rewarded(
+                survey_id,
+                participant
+            ));
+        }
+        assert_eq!(get_survey(survey_id).rewarded_count, 3);
+        assert_eq!(get_survey(survey_id).escrow_remaining, 700);
+    });
+}
+
+// funding overflow
+#[test]
+fn fund_survey_fails_on_funding_overflow() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        // Funding already recorded close to the maximum
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().funded_amount = Some(u128::MAX - 999);
+        });
+
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::DefensiveUnexpectedOverflow
+        );
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            999
+        ));
+        assert_eq!(get_survey(survey_id).funded_amount, Some(u128::MAX));
+    });
+}
+
+// surveys_joined_by
+#[test]
+fn surveys_joined_by_tracks_joins_and_removals() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000
+            ));
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        let mut joined = PalletSurvey::surveys_joined_by(participant_id);
+        joined.sort();
+        assert_eq!(joined, vec![0, 1]);
+        assert!(PalletSurvey::surveys_joined_by(3).is_empty());
+
+        assert_ok!(PalletSurvey::withdraw_participation(
+            RuntimeOrigin::signed(participant_id),
+            0
+        ));
+        assert_eq!(PalletSurvey::surveys_joined_by(participant_id), vec![1]);
+
+        assert_ok!(PalletSurvey::clear_participants(
+            RuntimeOrigin::signed(survey_owner),
+            1
+        ));
+        assert!(PalletSurvey::surveys_joined_by(participant_id).is_empty());
+    });
+}
+
+// set_participant_score
+#[test]
+fn reward_participant_scales_reward_by_score() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::set_participant_score(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            50
+        ));
+        let participant_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            participant_balance_before + 50
+        );
+    });
+}
+
+#[test]
+fn set_participant_score_fails_invalid_score() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::set_participant_score(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id,
+                101
+            ),
+            crate::Error::<Test>::InvalidScore
+        );
+    });
+}
+
+#[test]
+fn reward_participant_pays_nothing_unscored_when_configured() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        UnscoredFullReward::set(false);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(PalletSurvey::reward_all_preview(survey_id), (1, 0));
+    });
+}
+
+// do_pay_reward
+#[test]
+fn do_pay_reward_rolls_back_when_vesting_fails() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit: ParticipantLimitType = 1000;
+        let fund_amount = 100000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit
+        ));
+        assert_ok!(PalletSurvey::set_vesting_blocks(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Some(10)
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            fund_amount
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        let escrow_before = get_survey(survey_id).escrow_remaining;
+
+        // Payment is called outside of a dispatchable, so nothing else would revert it
+        FailVesting::set(true);
+        assert!(PalletSurvey::do_pay_reward(&get_survey(survey_id), participant_id, 100).is_err());
+
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before
+        );
+        assert_eq!(get_survey(survey_id).escrow_remaining, escrow_before);
+        assert_eq!(get_survey(survey_id).rewarded_count, 0);
+
+        // Payment can be retried once vesting succeeds
+        FailVesting::set(false);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before + 100
+        );
+    });
+}
+
+// escrow_surplus
+#[test]
+fn escrow_surplus_excludes_rewards_owed_to_registered_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_eq!(PalletSurvey::escrow_surplus(survey_id), Some(1000));
+
+        for participant in [participant_id, 3, 4] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+        assert_eq!(PalletSurvey::escrow_surplus(survey_id), Some(700));
+
+        // Paying a registered participant leaves the surplus unchanged
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 900);
+        assert_eq!(PalletSurvey::escrow_surplus(survey_id), Some(700));
+
+        assert_eq!(PalletSurvey::escrow_surplus(1), None);
+    });
+}
+
+// set_min_participants
+#[test]
+fn reward_participant_requires_min_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_ok!(PalletSurvey::set_min_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyMinParticipantsSet {
+                survey_id,
+                seq: 1,
+                min_participants: 2,
+            })
+        );
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Below the threshold
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::MinimumParticipantsNotMet
+        );
+
+        // At the threshold
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+    });
+}
+
+#[test]
+fn cancel_survey_below_min_participants_refunds_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_ok!(PalletSurvey::set_min_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            5
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_owner_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), survey_id));
+
+        // Whole escrow is refunded, on top of the unslashed deposit
+        let balance_owner_after =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+        assert!(balance_owner_after >= balance_owner_before + 1000);
+        assert_eq!(get_survey(survey_id).escrow_remaining, 0);
+    });
+}
+
+#[test]
+fn set_min_participants_fails_above_participants_limit() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_noop!(
+            PalletSurvey::set_min_participants(RuntimeOrigin::signed(survey_owner), survey_id, 11),
+            crate::Error::<Test>::MinParticipantsAboveLimit
+        );
+    });
+}
+
+// set_distribute_remainder
+#[test]
+fn distribute_remainder_pays_one_more_to_first_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_distribute_remainder(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10001
+        ));
+        assert_eq!(get_survey(survey_id).reward_amount, Some(10));
+        assert_eq!(get_survey(survey_id).reward_remainder, 1);
+
+        for participant in [participant_id, 3, 4] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+
+        let balances_before = [participant_id, 3, 4].map(|participant| {
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant)
+        });
+        for participant in [participant_id, 3, 4] {
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+        let balances_after = [participant_id, 3, 4].map(|participant| {
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant)
+        });
+
+        // Only the first registered participant earns the remainder
+        assert_eq!(balances_after[0] - balances_before[0], 11);
+        assert_eq!(balances_after[1] - balances_before[1], 10);
+        assert_eq!(balances_after[2] - balances_before[2], 10);
+        assert_eq!(get_survey(survey_id).escrow_remaining, 10001 - 31);
+    });
+}
+
+#[test]
+fn remainder_stays_in_escrow_by_default() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000,
+            10001
+        ));
+        assert_eq!(get_survey(survey_id).reward_remainder, 0);
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before + 10
+        );
+    });
+}
+
+// register_participant after deadline
+#[test]
+fn register_participant_fails_after_deadline_before_sweep() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_survey_deadline(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+
+        // Registration is still open on the deadline block
+        System::set_block_number(10);
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Survey is still active, no sweep having run
+        System::set_block_number(11);
+        assert_eq!(get_survey(survey_id).status, Status::Active);
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 3),
+            crate::Error::<Test>::SurveyExpired
+        );
+    });
+}
+
+// distribute_rewards
+#[test]
+fn distribute_rewards_fifo_pays_earliest_registrants_first() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant in [4, 2, 3] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+        assert_eq!(PalletSurvey::reward_queue(survey_id), vec![4, 2, 3]);
+
+        assert_ok!(PalletSurvey::distribute_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, 4));
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, 2));
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, 3));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::BatchRewarded {
+                survey_id,
+                seq: 6,
+                count: 2,
+                total: 200,
+            })
+        );
+        assert_eq!(PalletSurvey::reward_queue(survey_id), vec![3]);
+    });
+}
+
+#[test]
+fn distribute_rewards_random_is_deterministic_for_a_seed() {
+    let random_queue = || {
+        let mut queue = Vec::new();
+        new_test_ext().execute_with(|| {
+            let (survey_owner, _) = initialize_state();
+            let survey_id: SurveyId = 0;
+            RandomSeed::set(sp_core::H256::repeat_byte(7));
+
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+            assert_ok!(PalletSurvey::set_distribution_order(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                crate::DistributionOrder::Random
+            ));
+            assert_ok!(PalletSurvey::fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                1000
+            ));
+            for participant in 2..6 {
+                assert_ok!(PalletSurvey::register_participant(
+                    RuntimeOrigin::signed(survey_owner),
+                    survey_id,
+                    participant
+                ));
+            }
+
+            queue = PalletSurvey::reward_queue(survey_id);
+            let mut sorted_queue = queue.clone();
+            sorted_queue.sort();
+            assert_eq!(sorted_queue, vec![2, 3, 4, 5]);
+
+            // The first participants of the queue are the ones paid
+            assert_ok!(PalletSurvey::distribute_rewards(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                2
+            ));
+            for (position, participant) in queue.iter().enumerate() {
+                assert_eq!(
+                    PalletSurvey::is_participant_already_rewarded(survey_id, *participant),
+                    position < 2
+                );
+            }
+        });
+        queue
+    };
+
+    assert_eq!(random_queue(), random_queue());
+}
+
+// reward_breakdown
+#[test]
+fn reward_breakdown_tracks_rewarded_and_pending_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_eq!(PalletSurvey::reward_breakdown(survey_id), None);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant in [participant_id, 3] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant
+            ));
+        }
+
+        // None rewarded
+        assert_eq!(
+            PalletSurvey::reward_breakdown(survey_id),
+            Some(RewardBreakdown { registered: 2, rewarded: 0, pending: 2, escrow_remaining: 1000 })
+        );
+
+        // Some rewarded
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            PalletSurvey::reward_breakdown(survey_id),
+            Some(RewardBreakdown { registered: 2, rewarded: 1, pending: 1, escrow_remaining: 900 })
+        );
+
+        // All rewarded
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+        assert_eq!(
+            PalletSurvey::reward_breakdown(survey_id),
+            Some(RewardBreakdown { registered: 2, rewarded: 2, pending: 0, escrow_remaining: 800 })
+        );
+    });
+}
+
+// fund_survey_as
+#[test]
+fn fund_survey_as_funds_from_non_owner_and_refunds_them() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let funder = 3;
+        let survey_id: SurveyId = 0;
+        let fund_amount = 1000;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+
+        let balance_owner_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+        let balance_funder_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&funder);
+
+        assert_ok!(PalletSurvey::fund_survey_as(
+            RuntimeOrigin::signed(funder),
+            survey_id,
+            fund_amount
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyFunded {
+                survey_id,
+                seq: 1,
+                funded_amount: fund_amount,
+                funder_id: funder,
+            })
+        );
+        assert_eq!(PalletSurvey::survey_funder(survey_id), Some(funder));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            balance_owner_before
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&funder),
+            balance_funder_before - fund_amount
+        );
+
+        // Rewarding stays restricted to the owner
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::reward_participant(RuntimeOrigin::signed(funder), survey_id, participant_id),
+            crate::Error::<Test>::NotAuthorized
+        );
+
+        // Escrow is refunded to the funder, the deposit to the owner
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), survey_id));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&funder),
+            balance_funder_before
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            balance_owner_before + 9
+        );
+    });
+}
+
+// try_state
+#[test]
+fn rewarded_participants_are_always_registered() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let unregistered = 3;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_noop!(
+            PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                unregistered
+            ),
+            crate::Error::<Test>::ParticipantNotRegistered
+        );
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, unregistered));
+        assert_ok!(PalletSurvey::do_try_state());
+
+        // Invariant catches a rewarded account which was never registered
+        crate::ParticipantsRewarded::<Test>::insert(survey_id, unregistered, true);
+        assert!(PalletSurvey::do_try_state().is_err());
+    });
+}
+
+// extend_participants_limit
+#[test]
+fn extend_participants_limit_redivides_reward_when_recomputing() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_recompute_reward_on_extend(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+
+        let balance_owner_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_ok!(PalletSurvey::extend_participants_limit(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            20
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::ParticipantsLimitExtended {
+                survey_id,
+                seq: 4,
+                new_limit: 20,
+                reward_amount: Some(50),
+            })
+        );
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.participants_limit, 20);
+        assert_eq!(survey.reward_amount, Some(50));
+        assert_eq!(survey.escrow_remaining, 1000);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            balance_owner_before
+        );
+
+        // Rewarded participants would have been paid the former reward
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::extend_participants_limit(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                40
+            ),
+            crate::Error::<Test>::CannotRecomputeAfterRewards
+        );
+    });
+}
+
+#[test]
+fn extend_participants_limit_funds_new_slots_by_default() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+
+        let balance_owner_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_ok!(PalletSurvey::extend_participants_limit(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            20
+        ));
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.participants_limit, 20);
+        assert_eq!(survey.reward_amount, Some(100));
+        assert_eq!(survey.escrow_remaining, 2000);
+        assert_eq!(survey.funded_amount, Some(2000));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            balance_owner_before - 1000
+        );
+
+        assert_noop!(
+            PalletSurvey::extend_participants_limit(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                20
+            ),
+            crate::Error::<Test>::LimitNotExtended
+        );
+    });
+}
+
+// status_changed_by
+#[test]
+fn set_survey_status_records_manager_who_changed_it() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let manager_id = 3;
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_ok!(PalletSurvey::add_manager(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            manager_id
+        ));
+        assert_eq!(get_survey(survey_id).status_changed_by, None);
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(manager_id),
+            survey_id,
+            Status::Paused
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyStatusUpdated {
+                survey_id,
+                seq: 2,
+                new_status: Status::Paused,
+                status_code: 2,
+                changed_by: manager_id,
+            })
+        );
+        assert_eq!(get_survey(survey_id).status_changed_by, Some(manager_id));
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Active
+        ));
+        assert_eq!(get_survey(survey_id).status_changed_by, Some(survey_owner));
+    });
+}
+
+// reward_amount_saturating
+#[test]
+fn reward_amount_saturating_divides_funding() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(PalletSurvey::reward_amount_saturating(1000, 10), 100);
+        assert_eq!(PalletSurvey::reward_amount_saturating(1005, 10), 100);
+    });
+}
+
+#[test]
+fn reward_amount_saturating_is_zero_for_zero_limit() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(PalletSurvey::reward_amount_saturating(1000, 0), 0);
+    });
+}
+
+// MinReward
+#[test]
+fn fund_survey_distinguishes_dust_reward_from_insufficient_funding() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        MinReward::set(5);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+
+        // Funding below the participant count
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 99),
+            crate::Error::<Test>::FundingInferiorNumberParticipants
+        );
+
+        // Funding covering every participant, but with a dust reward
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 499),
+            crate::Error::<Test>::RewardBelowMinimum
+        );
+
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 500));
+        assert_eq!(get_survey(survey_id).reward_amount, Some(5));
+    });
+}
+
+// announce
+#[test]
+fn announce_emits_message_without_storing_it() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        let survey_before = get_survey(survey_id);
+
+        let message: BoundedVec<u8, _> = BoundedVec::try_from(b"rewards sent".to_vec()).unwrap();
+        assert_ok!(PalletSurvey::announce(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            message.clone()
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyAnnouncement {
+                survey_id,
+                seq: 1,
+                message,
+            })
+        );
+        assert_eq!(get_survey(survey_id), survey_before);
+    });
+}
+
+#[test]
+fn announce_rejects_over_length_message() {
+    new_test_ext().execute_with(|| {
+        let too_long: Result<BoundedVec<u8, <Test as Config>::MaxAnnouncementLen>, _> =
+            BoundedVec::try_from(vec![0u8; 17]);
+        assert!(too_long.is_err());
+    });
+}
+
+#[test]
+fn announce_fails_not_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_noop!(
+            PalletSurvey::announce(
+                RuntimeOrigin::signed(participant_id),
+                survey_id,
+                BoundedVec::try_from(b"hello".to_vec()).unwrap()
+            ),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// clawback_reward
+#[test]
+fn clawback_reward_returns_reward_to_escrow_within_window() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let reason_hash = sp_core::H256::repeat_byte(1);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            PalletSurvey::reward_receipt(survey_id, participant_id),
+            Some(crate::RewardReceipt { amount: 100, fee: 0, block: 1 })
+        );
+
+        System::set_block_number(11);
+        assert_ok!(PalletSurvey::clawback_reward(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            reason_hash
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardClawedBack {
+                survey_id,
+                seq: 5,
+                participant_id,
+                amount: 100,
+                reason_hash,
+            })
+        );
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before
+        );
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.escrow_remaining, 1000);
+        assert_eq!(survey.rewarded_count, 0);
+        assert_eq!(survey.number_participants, 0);
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+        assert_eq!(PalletSurvey::lifetime_rewards(participant_id), 0);
+    });
+}
+
+#[test]
+fn clawback_reward_fails_after_window() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        System::set_block_number(12);
+        assert_noop!(
+            PalletSurvey::clawback_reward(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id,
+                sp_core::H256::zero()
+            ),
+            crate::Error::<Test>::ClawbackWindowExpired
+        );
+    });
+}
+
+// set_reward_rounding
+
+#[test]
+fn ceil_rounding_requires_funding_rounded_up_rewards() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+        assert_ok!(PalletSurvey::set_reward_rounding(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            crate::RewardRounding::Ceil
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyRewardRoundingSet {
+                survey_id,
+                seq: 1,
+                rounding: crate::RewardRounding::Ceil,
+            })
+        );
+
+        // 1000 ceils up to a reward of 334, which 3 participants make 1002
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::FundingInsufficientForCeilReward
+        );
+
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1002
+        ));
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.reward_amount, 334);
+        assert_eq!(survey.escrow_remaining, 1002);
+    });
+}
+
+#[test]
+fn set_reward_rounding_fails_once_funded() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::set_reward_rounding(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                crate::RewardRounding::Ceil
+            ),
+            crate::Error::<Test>::SurveyAlreadyFunded
+        );
+    });
+}
+
+// pending_claims
+#[test]
+fn pending_claims_lists_unrewarded_participations() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        for (survey_id, fund_amount) in [(0, 1000), (1, 2000), (2, 3000)] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                fund_amount
+            ));
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            participant_id
+        ));
+
+        let mut pending = PalletSurvey::pending_claims(participant_id);
+        pending.sort();
+        assert_eq!(pending, vec![(0, 100), (1, 200)]);
+        assert!(PalletSurvey::pending_claims(3).is_empty());
+    });
+}
+
+// clone_survey
+#[test]
+fn clone_survey_copies_parameters_and_resets_state() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let source_id: SurveyId = 0;
+        let new_id: SurveyId = 1;
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            10
+        ));
+        assert_ok!(PalletSurvey::set_early_bird(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            2,
+            3
+        ));
+        assert_ok!(PalletSurvey::set_vesting_blocks(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            Some(20)
+        ));
+        assert_ok!(PalletSurvey::set_min_participants(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            5
+        ));
+        assert_ok!(PalletSurvey::set_survey_visibility(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            false
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            participant_id
+        ));
+
+        assert_ok!(PalletSurvey::clone_survey(
+            RuntimeOrigin::signed(survey_owner),
+            source_id,
+            new_id
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurveyCreated {
+                survey_id: new_id,
+                seq: 0,
+                owner_id: survey_owner,
+            })
+        );
+
+        let source = get_survey(source_id);
+        let survey = get_survey(new_id);
+        assert_eq!(survey.owner_id, survey_owner);
+        assert_eq!(survey.participants_limit, 10);
+        assert_eq!(survey.early_bird_count, 2);
+        assert_eq!(survey.early_bird_multiplier, 3);
+        assert_eq!(survey.vesting_blocks, Some(20));
+        assert_eq!(survey.min_participants, 5);
+        assert!(!survey.is_public);
+        assert!(source.is_funded);
+        assert!(!survey.is_funded);
+        assert_eq!(survey.funded_amount, None);
+        assert_eq!(survey.escrow_remaining, 0);
+        assert_eq!(survey.number_participants, 0);
+        assert_eq!(survey.status, Status::Active);
+        assert!(!PalletSurvey::is_participant(new_id, participant_id));
+    });
+}
+
+#[test]
+fn clone_survey_fails_for_existing_id_or_other_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+        }
+
+        assert_noop!(
+            PalletSurvey::clone_survey(RuntimeOrigin::signed(survey_owner), 0, 1),
+            crate::Error::<Test>::SurveyAlreadyCreated
+        );
+        assert_noop!(
+            PalletSurvey::clone_survey(RuntimeOrigin::signed(other), 0, 2),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+#[test]
+fn register_participant_never_exceeds_limit_within_a_block() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants_limit = 5;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants_limit,
+            1000
+        ));
+
+        let registered = (10..10 + participants_limit as u64 + 5)
+            .filter(|participant_id| {
+                PalletSurvey::register_participant(
+                    RuntimeOrigin::signed(survey_owner),
+                    survey_id,
+                    *participant_id,
+                )
+                .is_ok()
+            })
+            .count();
+
+        assert_eq!(registered as u128, participants_limit);
+        assert_eq!(get_survey(survey_id).number_participants, participants_limit);
+    });
+}
+
+// RewardFee
+#[test]
+fn reward_participant_pays_fee_to_treasury() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        RewardFee::set(sp_runtime::Perbill::from_percent(10));
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        let participant_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        let treasury_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY);
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardClaimed {
+                survey_id,
+                seq: 4,
+                participant_id,
+                reward_amount: 90,
+            })
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            participant_balance_before + 90
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY),
+            treasury_balance_before + 10
+        );
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.escrow_remaining, 900);
+        assert_eq!(survey.total_paid, 100);
+        assert_eq!(PalletSurvey::lifetime_rewards(participant_id), 90);
+    });
+}
+
+// creation_flow
+#[test]
+fn creation_flow_matches_creation_call() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            1000
+        ));
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            10,
+            1000
+        ));
+
+        assert_eq!(get_survey(0).creation_flow, crate::CreationFlow::Separate);
+        assert_eq!(get_survey(1).creation_flow, crate::CreationFlow::Combined);
+    });
+}
+
+// DenseParticipants
+fn register_and_observe_participants(dense: bool) -> (Vec<bool>, Vec<u64>, Vec<u64>, u128) {
+    new_test_ext().execute_with(|| {
+        DenseParticipants::set(dense);
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in [participant_id, 3, 4, 5] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_noop!(
+            PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ),
+            crate::Error::<Test>::ParticipantAlreadyRegistered
+        );
+        assert_ok!(PalletSurvey::withdraw_participation(
+            RuntimeOrigin::signed(4),
+            survey_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let registered = (1..7)
+            .map(|participant_id| PalletSurvey::is_participant(survey_id, participant_id))
+            .collect();
+        let mut participants = PalletSurvey::participants_of(survey_id);
+        participants.sort();
+        let mut queue = PalletSurvey::reward_queue(survey_id);
+        queue.sort();
+        (registered, participants, queue, PalletSurvey::reward_all_preview(survey_id).1)
+    })
+}
+
+#[test]
+fn dense_participants_match_map_backend() {
+    let map = register_and_observe_participants(false);
+    let dense = register_and_observe_participants(true);
+
+    assert_eq!(map, dense);
+    assert_eq!(dense.0, vec![false, true, true, false, true, false]);
+    assert_eq!(dense.1, vec![2, 3, 5]);
+    assert_eq!(dense.2, vec![3, 5]);
+    assert_eq!(dense.3, 200);
+}
+
+#[test]
+fn dense_participants_are_removed_on_clear() {
+    new_test_ext().execute_with(|| {
+        DenseParticipants::set(true);
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in 2..7 {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        // At most `MaxClear` participants are drained from the set per call
+        assert_ok!(PalletSurvey::clear_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+        assert_eq!(PalletSurvey::participants_of(survey_id).len(), 2);
+        assert_eq!(get_survey(survey_id).number_participants, 2);
+        assert_ok!(PalletSurvey::clear_participants(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id
+        ));
+
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+        assert!(PalletSurvey::participants_of(survey_id).is_empty());
+        assert_eq!(get_survey(survey_id).number_participants, 0);
+    });
+}
+
+// set_survey_status to Completed
+#[test]
+fn completing_survey_closes_registration() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.status, Status::Completed);
+        assert_eq!(survey.completed_at, Some(1));
+        assert_eq!(survey.status_changed_by, Some(survey_owner));
+
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 3),
+            crate::Error::<Test>::SurveyCompleted
+        );
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+    });
+}
+
+#[test]
+fn completing_post_hoc_survey_computes_rewards() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants = [2, 3, 4, 5];
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            100
+        ));
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            true
+        ));
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1000
+        ));
+        for participant_id in participants.iter() {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                *participant_id
+            ));
+        }
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_eq!(get_survey(survey_id).reward_amount, Some(250));
+        assert_eq!(PalletSurvey::cached_reward_amount(survey_id), Some(250));
+
+        let participant_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participants[0]);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants[0]
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participants[0]),
+            participant_balance_before + 250
+        );
+        assert_noop!(
+            PalletSurvey::finalize_rewards(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::RewardsAlreadyFinalized
+        );
+    });
+}
+
+// YieldSource
+#[test]
+fn completing_survey_shares_accrued_yield_between_participants() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants = [2, 3, 4];
+        AccruedYield::set(100);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in participants.iter() {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                *participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants[0]
+        ));
+        let balances_before: Vec<_> = participants
+            .iter()
+            .map(|participant_id| {
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id)
+            })
+            .collect();
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert!(get_events().contains(&Event::YieldDistributed {
+            survey_id,
+            seq: 7,
+            amount: 99,
+            participants: 3,
+        }));
+
+        for (participant_id, balance_before) in participants.iter().zip(balances_before) {
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id),
+                balance_before + 33
+            );
+        }
+        // Left over by the division
+        assert_eq!(get_survey(survey_id).escrow_remaining, 900 + 1);
+
+        // Base rewards are paid on top of the yield
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants[1]
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participants[1]),
+            1000000000 + 33 + 100
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "defensive error happened: DefensiveRewardAmountMissing, survey_id = 0")]
+fn reward_participant_fails_loudly_without_reward_amount() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        // Corrupt the survey, funded but without reward amount
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().reward_amount = None
+        });
+        crate::RewardAmounts::<Test>::remove(survey_id);
+        assert!(get_survey(survey_id).is_funded);
+
+        let _ = PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+        );
+    });
+}
+
+// total_surveys
+#[test]
+fn total_surveys_tracks_creations_and_archives() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        assert_eq!(PalletSurvey::total_surveys(), 0);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            1,
+            1000
+        ));
+        for survey_id in [1, 2] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+        }
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 1, 10),
+            crate::Error::<Test>::SurveyAlreadyCreated
+        );
+        assert_eq!(PalletSurvey::total_surveys(), 3);
+
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            Status::Completed
+        ));
+        assert_ok!(PalletSurvey::archive_survey(RuntimeOrigin::signed(survey_owner), 0));
+        assert_eq!(PalletSurvey::total_surveys(), 2);
+    });
+}
+
+// batch_set_status
+#[test]
+fn batch_set_status_updates_every_survey() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+        }
+
+        let updates: BoundedVec<_, _> =
+            vec![(0, Status::Paused), (1, Status::Paused)].try_into().unwrap();
+        assert_ok!(PalletSurvey::batch_set_status(
+            RuntimeOrigin::signed(survey_owner),
+            updates
+        ));
+
+        assert_eq!(get_survey(0).status, Status::Paused);
+        assert_eq!(get_survey(1).status, Status::Paused);
+        let events = get_events();
+        for survey_id in [0, 1] {
+            assert!(events.contains(&Event::SurveyStatusUpdated {
+                survey_id,
+                seq: 1,
+                new_status: Status::Paused,
+                status_code: 2,
+                changed_by: survey_owner,
+            }));
+        }
+
+        let updates: BoundedVec<_, _> = vec![(0, Status::Active)].try_into().unwrap();
+        assert_ok!(PalletSurvey::batch_set_status(
+            RuntimeOrigin::signed(survey_owner),
+            updates
+        ));
+        assert_eq!(get_survey(0).status, Status::Active);
+    });
+}
+
+#[test]
+fn batch_set_status_fails_atomically_on_survey_of_other_owner() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10
+        ));
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(other_owner),
+            1,
+            10
+        ));
+
+        let updates: BoundedVec<_, _> =
+            vec![(0, Status::Paused), (1, Status::Paused)].try_into().unwrap();
+        assert_noop!(
+            PalletSurvey::batch_set_status(RuntimeOrigin::signed(survey_owner), updates),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+        assert_eq!(get_survey(0).status, Status::Active);
+        assert_eq!(get_survey(1).status, Status::Active);
+    });
+}
+
+// reward_receipt
+#[test]
+fn reward_receipt_is_stored_on_reward() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            4,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert!(PalletSurvey::reward_receipt(survey_id, participant_id).is_none());
+
+        System::set_block_number(3);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_eq!(
+            PalletSurvey::reward_receipt(survey_id, participant_id),
+            Some(crate::RewardReceipt { amount: 250, fee: 0, block: 3 })
+        );
+        assert!(PalletSurvey::reward_receipt(survey_id, 3).is_none());
+    });
+}
+
+// MaxTotalEscrow
+#[test]
+fn funding_fails_beyond_max_total_escrow() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        MaxTotalEscrow::set(2000);
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000
+            ));
+        }
+        assert_eq!(PalletSurvey::total_escrow(), 2000);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            1
+        ));
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 2, 100),
+            crate::Error::<Test>::TotalEscrowCapExceeded
+        );
+
+        // Rewards release escrow
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_eq!(PalletSurvey::total_escrow(), 1900);
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            100
+        ));
+        assert_eq!(PalletSurvey::total_escrow(), 2000);
+
+        // Refunds release escrow
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), 1));
+        assert_eq!(PalletSurvey::total_escrow(), 1000);
+    });
+}
+
+// Status::code
+#[test]
+fn status_codes_are_stable() {
+    assert_eq!(Status::Active.code(), 1);
+    assert_eq!(Status::Paused.code(), 2);
+    assert_eq!(Status::Completed.code(), 3);
+    assert_eq!(Status::Cancelled.code(), 4);
+}
+
+// Keyless participants
+#[test]
+fn reward_participant_pays_multisig_account() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        // Derived like `pallet_multisig` accounts, which have no key to sign with
+        let entropy = (b"modlpy/utilisuba", vec![3u64, 4, 5], 2u16)
+            .using_encoded(sp_core::hashing::blake2_256);
+        let multisig = <AccountId<Test> as codec::Decode>::decode(
+            &mut sp_runtime::traits::TrailingZeroInput::new(entropy.as_ref()),
+        )
+        .unwrap();
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&multisig),
+            0
+        );
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            multisig
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            multisig
+        ));
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&multisig),
+            100
+        );
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, multisig));
+    });
+}
+
+// FundingSource
+#[test]
+fn fund_survey_keeps_existential_deposit() {
+    new_test_ext().execute_with(|| {
+        initialize_state();
+        let survey_owner: AccountId<Test> = 20;
+        let survey_id: SurveyId = 0;
+        assert_ok!(<<Test as Config>::NativeBalance as fungible::Mutate<
+            AccountId<Test>,
+        >>::mint_into(&survey_owner, 1010));
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            1000
+        );
+
+        // The whole balance would leave the owner below the existential deposit
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+
+        assert_ok!(PalletSurvey::fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            999
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            ExistentialDeposit::get()
+        );
+    });
+}
+
+// BlockRewardSummary
+#[test]
+fn on_finalize_summarizes_rewards_of_the_block() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000 * (survey_id + 1)
+            ));
+        }
+        for (survey_id, participant_id) in [(0, 2), (0, 3), (1, 2)] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        PalletSurvey::on_finalize(1);
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::BlockRewardSummary {
+                total_rewards_paid: 400,
+                participants_paid: 3,
+            })
+        );
+
+        // Counters are reset for the next block
+        System::set_block_number(2);
+        System::reset_events();
+        PalletSurvey::on_finalize(2);
+        assert!(get_events().is_empty());
+        assert_eq!(crate::BlockRewards::<Test>::get(), (0, 0));
+    });
+}
+
+// transfer_surplus
+#[test]
+fn transfer_surplus_moves_escrow_between_surveys() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000
+            ));
+        }
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+
+        // Surplus can only be moved out of ended surveys
+        assert_noop!(
+            PalletSurvey::transfer_surplus(RuntimeOrigin::signed(survey_owner), 0, 1, 500),
+            crate::Error::<Test>::SurveyNotEnded
+        );
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            Status::Completed
+        ));
+        assert_noop!(
+            PalletSurvey::transfer_surplus(RuntimeOrigin::signed(survey_owner), 0, 1, 901),
+            crate::Error::<Test>::AmountExceedsSurplus
+        );
+
+        assert_ok!(PalletSurvey::transfer_surplus(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            1,
+            500
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::SurplusTransferred {
+                survey_id: 0,
+                seq: 6,
+                to_survey: 1,
+                amount: 500,
+            })
+        );
+        assert_eq!(get_survey(0).escrow_remaining, 400);
+        let destination = get_survey(1);
+        assert_eq!(destination.escrow_remaining, 1500);
+        assert_eq!(destination.funded_amount, Some(1500));
+        // The destination is still open, so its reward shares the amount
+        assert_eq!(destination.reward_amount, Some(150));
+        assert_eq!(PalletSurvey::cached_reward_amount(1), Some(150));
+    });
+}
+
+#[test]
+fn transfer_surplus_keeps_escrow_owed_to_pending_early_birds() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        RewardFee::set(Perbill::from_percent(10));
+
+        // 12 reward slots of 100, the early bird earning 3 of them
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10));
+        assert_ok!(PalletSurvey::set_early_bird(RuntimeOrigin::signed(survey_owner), 0, 1, 3));
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1200));
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            1,
+            10,
+            1000
+        ));
+        for participant_id in [participant_id, 3] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                0,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            Status::Completed
+        ));
+
+        // The early bird is owed 300 and the other participant 100, fee included
+        assert_eq!(PalletSurvey::escrow_surplus(0), Some(800));
+        assert_noop!(
+            PalletSurvey::transfer_surplus(RuntimeOrigin::signed(survey_owner), 0, 1, 801),
+            crate::Error::<Test>::AmountExceedsSurplus
+        );
+        assert_ok!(PalletSurvey::transfer_surplus(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            1,
+            800
+        ));
+        assert_eq!(get_survey(0).escrow_remaining, 400);
+        let destination = get_survey(1);
+        assert_eq!(destination.escrow_remaining, 1800);
+        assert_eq!(destination.reward_amount, Some(180));
+        assert_eq!(PalletSurvey::cached_reward_amount(1), Some(180));
+
+        // Pending participants are still paid in full
+        for participant_id in [participant_id, 3] {
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                0,
+                participant_id
+            ));
+            assert!(PalletSurvey::is_participant_already_rewarded(0, participant_id));
+        }
+        assert_eq!(get_survey(0).escrow_remaining, 0);
+        assert_eq!(PalletSurvey::escrow_surplus(0), Some(0));
+    });
+}
+
+#[test]
+fn transfer_surplus_fails_across_owners() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, other_owner) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            Status::Completed
+        ));
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(other_owner),
+            1,
+            10,
+            1000
+        ));
+
+        assert_noop!(
+            PalletSurvey::transfer_surplus(RuntimeOrigin::signed(survey_owner), 0, 1, 500),
+            crate::Error::<Test>::NotOwnerOfSurvey
+        );
+    });
+}
+
+// EventVerbosity
+#[test]
+fn minimal_event_verbosity_suppresses_registration_events() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        EventVerbosity::set(crate::EventLevel::Minimal);
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10));
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1000));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+
+        let events = get_events();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::SurveyCreated { survey_id: 0, .. })));
+        assert!(events
+            .iter()
+            .all(|event| !matches!(event, Event::NewParticipantRegistered { .. })));
+        // Suppressed events still take their sequence number
+        assert_eq!(crate::EventSeq::<Test>::get(0), 3);
+    });
+}
+
+#[test]
+fn full_event_verbosity_deposits_registration_events() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10));
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1000));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            participant_id
+        ));
+
+        assert!(get_events()
+            .iter()
+            .any(|event| matches!(event, Event::NewParticipantRegistered { survey_id: 0, .. })));
+    });
+}
+
+// zero participants limit
+#[test]
+fn create_and_fund_survey_fails_with_zero_participants_limit() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let owner_balance =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner);
+
+        assert_noop!(
+            PalletSurvey::create_and_fund_survey(RuntimeOrigin::signed(survey_owner), 0, 0, 1000),
+            crate::Error::<Test>::ZeroParticipantsLimit
+        );
+        assert!(PalletSurvey::get_survey(0).is_none());
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            owner_balance
+        );
+    });
+}
+
+// escrow drain
+#[test]
+fn batch_and_single_rewards_share_escrow_within_a_block() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in [2, 3, 4] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+
+        // Leave escrow for two and a half rewards of 100
+        crate::SurveysMap::<Test>::mutate(survey_id, |survey| {
+            survey.as_mut().unwrap().escrow_remaining = 250
+        });
+
+        assert_ok!(PalletSurvey::batch_reward_silent(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            BoundedVec::truncate_from(vec![2, 3])
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 50);
+
+        let balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&4);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            4
+        ));
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, 4));
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.escrow_remaining, 50);
+        assert_eq!(survey.total_paid, 200);
+        assert_eq!(survey.rewarded_count, 2);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&4),
+            balance_before
+        );
+    });
+}
+
+// reward_utilization
+#[test]
+fn reward_utilization_tracks_rewards_paid() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), survey_id, 4));
+        assert_eq!(PalletSurvey::reward_utilization(survey_id), None);
+        assert_eq!(PalletSurvey::reward_utilization(1), None);
+
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000));
+        assert_eq!(PalletSurvey::reward_utilization(survey_id), Some(Perbill::zero()));
+
+        for participant_id in [2, 3, 4, 5] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        for participant_id in [2, 3] {
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_eq!(PalletSurvey::reward_utilization(survey_id), Some(Perbill::from_percent(50)));
+
+        for participant_id in [4, 5] {
+            assert_ok!(PalletSurvey::reward_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_eq!(PalletSurvey::reward_utilization(survey_id), Some(Perbill::one()));
+    });
+}
+
+// update_response_hash
+#[test]
+fn update_response_hash_works() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let response_hash = sp_core::H256::repeat_byte(1);
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::update_response_hash(
+                RuntimeOrigin::signed(participant_id),
+                survey_id,
+                response_hash
+            ),
+            crate::Error::<Test>::ParticipantNotRegistered
+        );
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_ok!(PalletSurvey::update_response_hash(
+            RuntimeOrigin::signed(participant_id),
+            survey_id,
+            response_hash
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::ResponseHashUpdated {
+                survey_id,
+                seq: 4,
+                participant_id,
+                response_hash,
+            })
+        );
+        assert_eq!(
+            PalletSurvey::participant_response(survey_id, participant_id),
+            Some(response_hash)
+        );
+
+        // Responses can only be updated while the survey is active
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Paused
+        ));
+        assert_noop!(
+            PalletSurvey::update_response_hash(
+                RuntimeOrigin::signed(participant_id),
+                survey_id,
+                sp_core::H256::repeat_byte(2)
+            ),
+            crate::Error::<Test>::SurveyPaused
+        );
+    });
+}
+
+#[test]
+fn update_response_hash_fails_once_rewarded() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_noop!(
+            PalletSurvey::update_response_hash(
+                RuntimeOrigin::signed(participant_id),
+                survey_id,
+                sp_core::H256::repeat_byte(1)
+            ),
+            crate::Error::<Test>::ParticipantAlreadyRewarded
+        );
+    });
+}
+
+// on_initialize expiries
+#[test]
+fn on_initialize_completes_expired_surveys_within_weight_limit() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        // Room for the cursor, one queue and three surveys without participants per block
+        let limit = db_weight.reads_writes(2, 2).saturating_add(db_weight.reads_writes(12, 12));
+        MaxExpiryWeight::set(limit);
+
+        for survey_id in 0..7 {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+            assert_ok!(PalletSurvey::set_survey_deadline(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                5
+            ));
+        }
+        let completed = || {
+            (0..7)
+                .filter(|survey_id| get_survey(*survey_id).status == Status::Completed)
+                .count()
+        };
+
+        // Nothing expires before the deadline has passed
+        System::set_block_number(5);
+        PalletSurvey::on_initialize(5);
+        assert_eq!(completed(), 0);
+
+        for (block, expected) in [(6, 3), (7, 6), (8, 7)] {
+            System::set_block_number(block);
+            let consumed = PalletSurvey::on_initialize(block);
+            assert!(consumed.all_lte(limit));
+            assert_eq!(completed(), expected);
+        }
+        assert_eq!(get_survey(6).completed_at, Some(8));
+        assert!(get_events().contains(&Event::SurveyAutoCompleted { survey_id: 6, seq: 2 }));
+    });
+}
+
+// fund_survey ordering
+#[test]
+fn fund_survey_failing_on_balance_leaves_no_partial_state() {
+    new_test_ext().execute_with(|| {
+        let _ = initialize_state();
+        let survey_owner: crate::mock::AccountId = 10;
+        let survey_id: SurveyId = 0;
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            510,
+        );
+        let asset_id = create_bonus_asset(survey_owner, 500);
+
+        assert_ok!(PalletSurvey::create_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            2
+        ));
+        assert_ok!(PalletSurvey::set_survey_bonus(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Some((asset_id, 100))
+        ));
+
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+
+        let survey = get_survey(survey_id);
+        assert!(!survey.is_funded);
+        assert_eq!(survey.escrow_remaining, 0);
+        assert_eq!(survey.bonus_escrow, 0);
+        assert_eq!(PalletSurvey::total_escrow(), 0);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&survey_owner),
+            500
+        );
+        assert_eq!(
+            <Assets as fungibles::Inspect<crate::mock::AccountId>>::balance(asset_id, &survey_owner),
+            500
+        );
+    });
+}
+
+// format_balance
+#[test]
+fn format_balance_splits_amounts_per_decimals() {
+    new_test_ext().execute_with(|| {
+        // The mock currency has 12 decimals
+        assert_eq!(PalletSurvey::format_balance(0), (0, 0));
+        assert_eq!(PalletSurvey::format_balance(999_999_999_999), (0, 999_999_999_999));
+        assert_eq!(PalletSurvey::format_balance(1_000_000_000_000), (1, 0));
+        assert_eq!(PalletSurvey::format_balance(12_500_000_000_000), (12, 500_000_000_000));
+    });
+}
+
+// dispute_reward
+fn reward_participant_of_new_survey(
+    survey_owner: crate::mock::AccountId,
+    participant_id: crate::mock::AccountId,
+    survey_id: SurveyId,
+) {
+    assert_ok!(PalletSurvey::create_and_fund_survey(
+        RuntimeOrigin::signed(survey_owner),
+        survey_id,
+        10,
+        1000
+    ));
+    assert_ok!(PalletSurvey::register_participant(
+        RuntimeOrigin::signed(survey_owner),
+        survey_id,
+        participant_id
+    ));
+    assert_ok!(PalletSurvey::reward_participant(
+        RuntimeOrigin::signed(survey_owner),
+        survey_id,
+        participant_id
+    ));
+}
+
+#[test]
+fn upheld_dispute_claws_back_reward_after_window() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        reward_participant_of_new_survey(survey_owner, participant_id, survey_id);
+
+        System::set_block_number(5);
+        assert_ok!(PalletSurvey::dispute_reward(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardDisputed { survey_id, seq: 5, participant_id })
+        );
+        assert_eq!(PalletSurvey::disputed_reward(survey_id, participant_id), Some(5));
+        assert_noop!(
+            PalletSurvey::dispute_reward(RuntimeOrigin::signed(survey_owner), survey_id, participant_id),
+            crate::Error::<Test>::RewardAlreadyDisputed
+        );
+
+        // Disputes raised within the window are resolved past it
+        System::set_block_number(20);
+        assert_ok!(PalletSurvey::resolve_dispute(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            true
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::DisputeResolved {
+                survey_id,
+                seq: 6,
+                participant_id,
+                upheld: true,
+                clawed_back: Some(100),
+            })
+        );
+
+        assert_eq!(PalletSurvey::disputed_reward(survey_id, participant_id), None);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before
+        );
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.escrow_remaining, 1000);
+        assert_eq!(survey.rewarded_count, 0);
+        assert!(!PalletSurvey::is_participant(survey_id, participant_id));
+    });
+}
+
+#[test]
+fn rejected_dispute_keeps_reward() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        reward_participant_of_new_survey(survey_owner, participant_id, survey_id);
+        let balance_participant_after_reward =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+
+        assert_noop!(
+            PalletSurvey::resolve_dispute(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id,
+                false
+            ),
+            crate::Error::<Test>::RewardNotDisputed
+        );
+        assert_ok!(PalletSurvey::dispute_reward(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::resolve_dispute(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            false
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::DisputeResolved {
+                survey_id,
+                seq: 6,
+                participant_id,
+                upheld: false,
+                clawed_back: None,
+            })
+        );
+
+        assert_eq!(PalletSurvey::disputed_reward(survey_id, participant_id), None);
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_after_reward
+        );
+        assert!(PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+    });
+}
+
+#[test]
+fn dispute_reward_fails_after_window() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        reward_participant_of_new_survey(survey_owner, participant_id, survey_id);
+
+        System::set_block_number(12);
+        assert_noop!(
+            PalletSurvey::dispute_reward(RuntimeOrigin::signed(survey_owner), survey_id, participant_id),
+            crate::Error::<Test>::ClawbackWindowExpired
+        );
+    });
+}
+
+// integrity_test
+#[test]
+fn integrity_test_accepts_mock_limits() {
+    new_test_ext().execute_with(|| {
+        <PalletSurvey as frame_support::traits::Hooks<u64>>::integrity_test();
+        assert!(<Test as Config>::MaxBatchSize::get() > 0);
+
+        // Limits are exposed in the metadata
+        let constants = PalletSurvey::pallet_constants_metadata();
+        for name in ["MaxBatchSize", "MaxSweep", "MaxClear", "MaxDenseParticipants"] {
+            assert!(constants.iter().any(|constant| constant.name == name));
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "`MinReward` must not exceed `MaxRewardPerParticipant`")]
+fn integrity_test_rejects_min_reward_above_cap() {
+    new_test_ext().execute_with(|| {
+        MinReward::set(100);
+        MaxRewardPerParticipant::set(99);
+        <PalletSurvey as frame_support::traits::Hooks<u64>>::integrity_test();
+    });
+}
+
+#[test]
+#[should_panic(expected = "`MinFunding` must not exceed `MaxTotalEscrow`")]
+fn integrity_test_rejects_min_funding_above_escrow_cap() {
+    new_test_ext().execute_with(|| {
+        MaxTotalEscrow::set(99);
+        <PalletSurvey as frame_support::traits::Hooks<u64>>::integrity_test();
+    });
+}
+
+// fundable balance
+#[test]
+fn fund_survey_fails_beyond_balance_left_by_other_surveys_and_holds() {
+    new_test_ext().execute_with(|| {
+        let _ = initialize_state();
+        let survey_owner: crate::mock::AccountId = 10;
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            1_101,
+        );
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+        }
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 0, 1000));
+
+        // 81 is left, the existential deposit of 1 being kept
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 100),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+
+        // Held funds cannot be committed either
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            1_181,
+        );
+        assert_ok!(<Balances as fungible::MutateHold<crate::mock::AccountId>>::hold(
+            &(),
+            &survey_owner,
+            1000
+        ));
+        assert_noop!(
+            PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 200),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+        assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), 1, 100));
+    });
+}
+
+// register_participant on a full survey
+#[test]
+fn register_participant_reports_status_before_capacity() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            1,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 4),
+            crate::Error::<Test>::MaxNumberOfParticipantsReached
+        );
+
+        // Existence and authorization still come first
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), 1, 4),
+            crate::Error::<Test>::SurveyNotCreated
+        );
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(5), survey_id, 4),
+            crate::Error::<Test>::NotAuthorized
+        );
+
+        // A full survey which is paused is reported as paused
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Paused
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 4),
+            crate::Error::<Test>::SurveyPaused
+        );
+
+        // Retrying an already applied registration stays a no-op
+        assert_ok!(PalletSurvey::register_participant_idempotent(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant_idempotent(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                4
+            ),
+            crate::Error::<Test>::SurveyPaused
+        );
+
+        // As is a full survey which is completed
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        assert_noop!(
+            PalletSurvey::register_participant(RuntimeOrigin::signed(survey_owner), survey_id, 4),
+            crate::Error::<Test>::SurveyCompleted
+        );
+    });
+}
+
+// set_survey_status to Cancelled
+#[test]
+fn set_survey_status_cannot_cancel() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, manager_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::add_manager(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            manager_id
+        ));
+
+        for caller in [survey_owner, manager_id] {
+            assert_noop!(
+                PalletSurvey::set_survey_status(
+                    RuntimeOrigin::signed(caller),
+                    survey_id,
+                    Status::Cancelled
+                ),
+                crate::Error::<Test>::CannotSetCancelledStatus
+            );
+        }
+
+        // Cancellation settles the escrow only through cancel_survey
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), survey_id));
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.status, Status::Cancelled);
+        assert_eq!(survey.escrow_remaining, 0);
+    });
+}
+
+#[test]
+fn batch_set_status_cannot_cancel() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000
+            ));
+        }
+
+        let updates: BoundedVec<_, _> =
+            vec![(0, Status::Cancelled), (1, Status::Paused)].try_into().unwrap();
+        assert_noop!(
+            PalletSurvey::batch_set_status(RuntimeOrigin::signed(survey_owner), updates),
+            crate::Error::<Test>::CannotSetCancelledStatus
+        );
+        assert_eq!(get_survey(0).escrow_remaining, 1000);
+    });
+}
+
+// clawback_reward with RewardFee
+#[test]
+fn clawback_reward_restores_gross_reward_with_fee() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let reason_hash = sp_core::H256::repeat_byte(1);
+        RewardFee::set(sp_runtime::Perbill::from_percent(10));
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        let participant_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        let treasury_balance_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY);
+
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            PalletSurvey::reward_receipt(survey_id, participant_id),
+            Some(crate::RewardReceipt { amount: 90, fee: 10, block: 1 })
+        );
+        assert_eq!(get_survey(survey_id).total_paid, 100);
+
+        assert_ok!(PalletSurvey::clawback_reward(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id,
+            reason_hash
+        ));
+        assert_eq!(
+            get_events().last(),
+            Some(&Event::RewardClawedBack {
+                survey_id,
+                seq: 5,
+                participant_id,
+                amount: 100,
+                reason_hash,
+            })
+        );
+
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            participant_balance_before
+        );
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&TREASURY),
+            treasury_balance_before
+        );
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.escrow_remaining, 1000);
+        assert_eq!(survey.total_paid, 0);
+        assert_eq!(PalletSurvey::total_escrow(), 1000);
+
+        // The reopened slot is fully paid again
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            3
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 900);
+    });
+}
+
+#[test]
+fn do_pay_reward_rolls_back_when_mint_fails() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+
+        let balance_participant_before =
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id);
+        let total_issuance = pallet_balances::TotalIssuance::<Test>::get();
+
+        // Minting the reward would overflow the total issuance
+        pallet_balances::TotalIssuance::<Test>::put(u128::MAX - 50);
+        assert!(PalletSurvey::do_pay_reward(&get_survey(survey_id), participant_id, 100).is_err());
+
+        assert!(!PalletSurvey::is_participant_already_rewarded(survey_id, participant_id));
+        assert!(PalletSurvey::reward_receipt(survey_id, participant_id).is_none());
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before
+        );
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1000);
+        assert_eq!(get_survey(survey_id).rewarded_count, 0);
+
+        // Payment can be retried once minting succeeds
+        pallet_balances::TotalIssuance::<Test>::put(total_issuance);
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participant_id),
+            balance_participant_before + 100
+        );
+    });
+}
+
+#[test]
+fn cancel_survey_fails_when_refund_cannot_be_minted() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+
+        // Not even the refunded deposit can be minted
+        pallet_balances::TotalIssuance::<Test>::put(u128::MAX - 5);
+        assert_noop!(
+            PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), survey_id),
+            crate::Error::<Test>::PayoutFailed
+        );
+    });
+}
+
+// FundingSource for deposits, fees and top-ups
+#[test]
+fn create_survey_takes_deposit_and_fee_from_fundable_balance() {
+    new_test_ext().execute_with(|| {
+        let _ = initialize_state();
+        let survey_owner: crate::mock::AccountId = 20;
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            1_010,
+        );
+        assert_ok!(<Balances as fungible::MutateHold<crate::mock::AccountId>>::hold(
+            &(),
+            &survey_owner,
+            1000
+        ));
+
+        // The deposit would take the existential deposit
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10),
+            crate::Error::<Test>::NotEnoughBalanceForDeposit
+        );
+
+        // So would the creation fee once the deposit is paid
+        CreationFee::set(50);
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            60,
+        );
+        assert_noop!(
+            PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10),
+            crate::Error::<Test>::CannotAffordCreationFee
+        );
+
+        let _ = <Balances as fungible::Mutate<crate::mock::AccountId>>::set_balance(
+            &survey_owner,
+            61,
+        );
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 0, 10));
+        assert_eq!(
+            <Balances as fungible::Inspect<crate::mock::AccountId>>::balance(&survey_owner),
+            1
+        );
+        assert_eq!(
+            <Balances as fungible::InspectHold<crate::mock::AccountId>>::balance_on_hold(
+                &(),
+                &survey_owner
+            ),
+            1000
+        );
+    });
+}
+
+#[test]
+fn top_up_escrow_takes_from_fundable_balance() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        let free_balance =
+            <Balances as fungible::Inspect<crate::mock::AccountId>>::balance(&survey_owner);
+        assert_ok!(<Balances as fungible::MutateHold<crate::mock::AccountId>>::hold(
+            &(),
+            &survey_owner,
+            free_balance - 100
+        ));
+
+        assert_noop!(
+            PalletSurvey::top_up_escrow(RuntimeOrigin::signed(survey_owner), survey_id, 100),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+        assert_ok!(PalletSurvey::top_up_escrow(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            99
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1099);
+    });
+}
+
+#[test]
+fn extend_participants_limit_funds_slots_from_fundable_balance() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        let free_balance =
+            <Balances as fungible::Inspect<crate::mock::AccountId>>::balance(&survey_owner);
+        assert_ok!(<Balances as fungible::MutateHold<crate::mock::AccountId>>::hold(
+            &(),
+            &survey_owner,
+            free_balance - 100
+        ));
+
+        // The new slot costs a reward of 100, which would take the existential deposit
+        assert_noop!(
+            PalletSurvey::extend_participants_limit(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                11
+            ),
+            crate::Error::<Test>::NotEnoughBalanceForFunding
+        );
+
+        assert_ok!(<Balances as fungible::MutateHold<crate::mock::AccountId>>::release(
+            &(),
+            &survey_owner,
+            1,
+            frame_support::traits::tokens::Precision::Exact
+        ));
+        assert_ok!(PalletSurvey::extend_participants_limit(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            11
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 1100);
+        assert_eq!(
+            <Balances as fungible::Inspect<crate::mock::AccountId>>::balance(&survey_owner),
+            1
+        );
+    });
+}
+
+// completion of expired surveys
+fn create_expiring_post_hoc_survey(
+    survey_owner: crate::mock::AccountId,
+    survey_id: SurveyId,
+    participants: &[crate::mock::AccountId],
+) {
+    assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), survey_id, 100));
+    assert_ok!(PalletSurvey::set_post_hoc_rewards(
+        RuntimeOrigin::signed(survey_owner),
+        survey_id,
+        true
+    ));
+    assert_ok!(PalletSurvey::fund_survey(RuntimeOrigin::signed(survey_owner), survey_id, 1000));
+    for participant_id in participants.iter() {
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            *participant_id
+        ));
+    }
+    assert_ok!(PalletSurvey::set_survey_deadline(
+        RuntimeOrigin::signed(survey_owner),
+        survey_id,
+        5
+    ));
+}
+
+#[test]
+fn sweep_expired_completes_like_set_survey_status() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, sweeper) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants = [3, 4, 5, 6];
+        AccruedYield::set(100);
+        create_expiring_post_hoc_survey(survey_owner, survey_id, &participants);
+
+        System::set_block_number(10);
+        assert_ok!(PalletSurvey::sweep_expired(
+            RuntimeOrigin::signed(sweeper),
+            BoundedVec::try_from(vec![survey_id]).unwrap()
+        ));
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.status, Status::Completed);
+        assert_eq!(survey.status_changed_by, Some(sweeper));
+        assert_eq!(survey.reward_amount, Some(250));
+        assert_eq!(PalletSurvey::cached_reward_amount(survey_id), Some(250));
+        assert!(get_events().iter().any(|event| matches!(
+            event,
+            Event::YieldDistributed { amount: 100, participants: 4, .. }
+        )));
+        for participant_id in participants.iter() {
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id),
+                1000000000 + 25
+            );
+        }
+    });
+}
+
+#[test]
+fn on_initialize_completes_like_set_survey_status() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _) = initialize_state();
+        let survey_id: SurveyId = 0;
+        let participants = [3, 4, 5, 6];
+        AccruedYield::set(100);
+        create_expiring_post_hoc_survey(survey_owner, survey_id, &participants);
+
+        System::set_block_number(5);
+        PalletSurvey::on_initialize(5);
+        System::set_block_number(6);
+        PalletSurvey::on_initialize(6);
+
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.status, Status::Completed);
+        assert_eq!(survey.status_changed_by, None);
+        assert_eq!(survey.reward_amount, Some(250));
+        let events = get_events();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::YieldDistributed { amount: 100, participants: 4, .. }
+        )));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Event::SurveyAutoCompleted { survey_id: 0, .. })));
+        for participant_id in participants.iter() {
+            assert_eq!(
+                <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(participant_id),
+                1000000000 + 25
+            );
+        }
+
+        // Participants are then rewarded the computed reward
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participants[0]
+        ));
+        assert_eq!(
+            <<Test as Config>::NativeBalance as fungible::Inspect<u64>>::balance(&participants[0]),
+            1000000000 + 25 + 250
+        );
+    });
+}
+
+// registration indexes
+#[test]
+fn registration_index_is_never_given_twice() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = 0;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        for participant_id in [participant_id, 3] {
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::withdraw_participation(
+            RuntimeOrigin::signed(participant_id),
+            survey_id
+        ));
+
+        // The withdrawn index is not given to the next participant
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            4
+        ));
+        assert_eq!(PalletSurvey::registration_index(survey_id, 3), Some(1));
+        assert_eq!(PalletSurvey::registration_index(survey_id, 4), Some(2));
+        assert_eq!(PalletSurvey::registration_count(survey_id), 3);
+        assert_eq!(get_survey(survey_id).number_participants, 2);
+    });
+}
+
+#[test]
+fn withdraw_participation_fails_survey_closed() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+
+        for survey_id in [0, 1] {
+            assert_ok!(PalletSurvey::create_and_fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10,
+                1000
+            ));
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            0,
+            Status::Completed
+        ));
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), 1));
+
+        assert_noop!(
+            PalletSurvey::withdraw_participation(RuntimeOrigin::signed(participant_id), 0),
+            crate::Error::<Test>::SurveyCompleted
+        );
+        assert_noop!(
+            PalletSurvey::withdraw_participation(RuntimeOrigin::signed(participant_id), 1),
+            crate::Error::<Test>::SurveyCancelled
+        );
+        assert!(PalletSurvey::is_participant(0, participant_id));
+    });
+}
+
+#[test]
+fn pending_claims_lists_only_payable_net_rewards() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        RewardFee::set(sp_runtime::Perbill::from_percent(10));
+
+        for survey_id in [0, 1, 2] {
+            assert_ok!(PalletSurvey::create_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                10
+            ));
+        }
+        // Survey 2 computes its reward once finalized
+        assert_ok!(PalletSurvey::set_post_hoc_rewards(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            true
+        ));
+        for survey_id in [0, 1, 2] {
+            assert_ok!(PalletSurvey::fund_survey(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                1000
+            ));
+            assert_ok!(PalletSurvey::register_participant(
+                RuntimeOrigin::signed(survey_owner),
+                survey_id,
+                participant_id
+            ));
+        }
+        assert_ok!(PalletSurvey::cancel_survey(RuntimeOrigin::signed(survey_owner), 1));
+
+        // The reward of 100 is paid net of the fee
+        assert_eq!(PalletSurvey::pending_claims(participant_id), vec![(0, 90)]);
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            2,
+            Status::Completed
+        ));
+        let mut pending = PalletSurvey::pending_claims(participant_id);
+        pending.sort();
+        assert_eq!(pending, vec![(0, 90), (2, 900)]);
+    });
+}
+
+// migrations
+
+#[test]
+fn migrate_to_v1_translates_surveys_and_participants() {
+    use crate::migrations::v1::{MigrateToV1, OldSurvey};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        StorageVersion::new(0).put::<PalletSurvey>();
+        let survey_id: SurveyId = 7;
+        let rewarded_id: crate::mock::AccountId = 3;
+
+        // Survey funded with 1000 for 10 participants, one of two participants rewarded
+        let old_survey = OldSurvey::<Test> {
+            survey_id,
+            owner_id: survey_owner,
+            participants_limit: 10,
+            number_participants: 2,
+            is_funded: true,
+            funded_amount: Some(1000),
+            reward_amount: Some(100),
+            status: Status::Active,
+        };
+        frame_support::storage::unhashed::put(
+            &crate::SurveysMap::<Test>::hashed_key_for(survey_id),
+            &old_survey,
+        );
+        crate::Participants::<Test>::insert(survey_id, participant_id, true);
+        crate::Participants::<Test>::insert(survey_id, rewarded_id, true);
+        crate::ParticipantsRewarded::<Test>::insert(survey_id, rewarded_id, true);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(PalletSurvey::on_chain_storage_version(), 1);
+        let survey = get_survey(survey_id);
+        assert_eq!(survey.owner_id, survey_owner);
+        assert_eq!(survey.number_participants, 2);
+        assert_eq!(survey.rewarded_count, 1);
+        assert_eq!(survey.total_paid, 100);
+        assert_eq!(survey.escrow_remaining, 900);
+        assert_eq!(survey.funder_id, Some(survey_owner));
+        assert_eq!(crate::TotalEscrow::<Test>::get(), 900);
+        assert_eq!(crate::SurveyCount::<Test>::get(), 1);
+        assert_eq!(PalletSurvey::next_survey_id(), 8);
+        assert_eq!(PalletSurvey::cached_reward_amount(survey_id), Some(100));
+        assert_eq!(PalletSurvey::registration_count(survey_id), 2);
+        assert!(crate::JoinedSurveys::<Test>::contains_key(participant_id, survey_id));
+        assert!(crate::RegistrationIndex::<Test>::contains_key(survey_id, participant_id));
+
+        // The remaining participant is paid from the rebuilt escrow
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(get_survey(survey_id).escrow_remaining, 800);
+
+        // Running again leaves storage untouched
+        MigrateToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(get_survey(survey_id).escrow_remaining, 800);
+        assert_eq!(PalletSurvey::registration_count(survey_id), 2);
     });
 }
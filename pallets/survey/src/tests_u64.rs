@@ -0,0 +1,70 @@
+use crate::{mock_u64::*, Event, Status};
+use frame_support::{assert_ok, traits::fungible};
+
+fn initialize_state() -> (AccountId, AccountId) {
+    // Go past genesis block so events get deposited
+    System::set_block_number(1);
+    for account in [1, 2, TREASURY] {
+        let _ = <Balances as fungible::Mutate<AccountId>>::set_balance(&account, 1_000_000_000);
+    }
+    (1, 2)
+}
+
+fn last_event() -> Option<Event<Test>> {
+    System::events().into_iter().rev().find_map(|record| match record.event {
+        RuntimeEvent::PalletSurvey(event) => Some(event),
+        _ => None,
+    })
+}
+
+#[test]
+fn surveys_run_with_u64_ids() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, participant_id) = initialize_state();
+        let survey_id: SurveyId = u64::MAX;
+
+        assert_ok!(PalletSurvey::create_and_fund_survey(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            10,
+            1000
+        ));
+        assert_ok!(PalletSurvey::register_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_ok!(PalletSurvey::reward_participant(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            participant_id
+        ));
+        assert_eq!(
+            last_event(),
+            Some(Event::RewardClaimed { survey_id, seq: 4, participant_id, reward_amount: 100 })
+        );
+
+        assert_ok!(PalletSurvey::set_survey_status(
+            RuntimeOrigin::signed(survey_owner),
+            survey_id,
+            Status::Completed
+        ));
+        let survey = PalletSurvey::get_survey(survey_id).unwrap();
+        assert_eq!(survey.survey_id, survey_id);
+        assert_eq!(survey.escrow_remaining, 900);
+        assert_eq!(PalletSurvey::surveys_of_owner(survey_owner), vec![survey_id]);
+    });
+}
+
+#[test]
+fn auto_ids_increment_u64_ids() {
+    new_test_ext().execute_with(|| {
+        let (survey_owner, _participant_id) = initialize_state();
+
+        assert_ok!(PalletSurvey::create_survey(RuntimeOrigin::signed(survey_owner), 7, 10));
+        assert_ok!(PalletSurvey::create_survey_auto_id(RuntimeOrigin::signed(survey_owner), 10));
+
+        assert!(PalletSurvey::get_survey(8).is_some());
+        assert_eq!(PalletSurvey::total_surveys(), 2);
+    });
+}
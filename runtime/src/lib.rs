@@ -13,7 +13,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, One, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
@@ -41,7 +42,7 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 use node_primitives::{AccountIndex, Moment};
@@ -109,7 +110,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -328,11 +329,103 @@ use frame_system::{EnsureRoot, EnsureSigned};
 /// We allow root to execute privileged asset operations.
 pub type AssetsForceOrigin = EnsureRoot<AccountId>;
 
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = AssetsForceOrigin;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = ConstU128<DOLLARS>;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 pub const UNITS: Balance = 1000000;
 
+parameter_types! {
+	pub TreasuryAccount: AccountId = PalletId(*b"py/trsry").into_account_truncating();
+	pub const SurveySweepReward: Balance = 1 * DOLLARS;
+	pub const SurveyMinFunding: Balance = 1 * DOLLARS;
+	pub const SurveyMinReward: Balance = 1 * CENTS;
+	pub const SurveyMaxTotalEscrow: Balance = 10_000_000 * DOLLARS;
+	pub const SurveyDeposit: Balance = 1 * DOLLARS;
+	pub const SurveyCancellationSlash: Perbill = Perbill::from_percent(10);
+	pub const SurveyRewardFee: Perbill = Perbill::zero();
+	pub const SurveyCreationFee: Balance = 10 * CENTS;
+	pub const SurveyMaxRewardPerParticipant: Balance = 1_000 * DOLLARS;
+	pub SurveyFeeDestination: Option<AccountId> = Some(TreasuryAccount::get());
+	// `DOLLARS` is 10^14 units
+	pub const SurveyRewardDecimals: u8 = 14;
+	pub const SurveyRewardDelay: BlockNumber = 10 * MINUTES;
+	pub const SurveyClawbackWindow: BlockNumber = 7 * DAYS;
+	pub const SurveyEventVerbosity: pallet_survey::EventLevel = pallet_survey::EventLevel::Full;
+	pub SurveyMaxExpiryWeight: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+}
+
+/// Randomness derived from the parent block hash.
+///
+/// Block producers can predict and influence it, which is acceptable for ordering survey rewards
+/// but not for anything of value.
+pub struct ParentHashRandomness;
+impl Randomness<Hash, BlockNumber> for ParentHashRandomness {
+	fn random(subject: &[u8]) -> (Hash, BlockNumber) {
+		let seed =
+			<BlakeTwo256 as sp_runtime::traits::Hash>::hash_of(&(subject, System::parent_hash()));
+		(seed, System::block_number())
+	}
+}
+
 impl pallet_survey::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type NativeBalance = Balances;
+    type SurveyId = u128;
+    type TreasuryAccount = TreasuryAccount;
+    type SweepReward = SurveySweepReward;
+    type MaxSweep = ConstU32<100>;
+    type MaxBatchSize = ConstU32<100>;
+    type MaxExpiryWeight = SurveyMaxExpiryWeight;
+    type MaxExpiriesPerBlock = ConstU32<1_000>;
+    type MaxAnnouncementLen = ConstU32<256>;
+    type MaxClear = ConstU32<100>;
+    type MinFunding = SurveyMinFunding;
+    type MinReward = SurveyMinReward;
+    type MaxTotalEscrow = SurveyMaxTotalEscrow;
+    type SurveyDeposit = SurveyDeposit;
+    type CancellationSlash = SurveyCancellationSlash;
+    type RewardFee = SurveyRewardFee;
+    type SlashDestination = TreasuryAccount;
+    type MaxScore = ConstU8<100>;
+    type UnscoredFullReward = ConstBool<true>;
+    type MaxRewardPerParticipant = SurveyMaxRewardPerParticipant;
+    type CreationFee = SurveyCreationFee;
+    type FeeDestination = SurveyFeeDestination;
+    type ForceCreateAccounts = ConstBool<false>;
+    type DenseParticipants = ConstBool<false>;
+    type MaxDenseParticipants = ConstU32<1_000>;
+    type Decimals = SurveyRewardDecimals;
+    type RewardDelay = SurveyRewardDelay;
+    type ClawbackWindow = SurveyClawbackWindow;
+    type BonusAssets = Assets;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type VestingHandler = ();
+    type OnSurveyLifecycle = ();
+    type YieldSource = ();
+    type ParticipantEligibility = ();
+    type FundingSource = pallet_survey::SpendableBalance<Runtime>;
+    type EventVerbosity = SurveyEventVerbosity;
+    type Randomness = ParentHashRandomness;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -346,7 +439,8 @@ construct_runtime!(
 		Nfts: pallet_nfts,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
-		Survey: pallet_survey
+		Survey: pallet_survey,
+		Assets: pallet_assets
 	}
 );
 
@@ -373,6 +467,9 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (pallet_survey::migrations::v1::MigrateToV1<Runtime>,);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -380,6 +477,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -611,6 +709,75 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_survey_runtime_api::SurveyApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn pending_owner(survey_id: u128) -> Option<AccountId> {
+			Survey::pending_owner(survey_id)
+		}
+
+		fn survey_funder(survey_id: u128) -> Option<AccountId> {
+			Survey::survey_funder(survey_id)
+		}
+
+		fn surveys_by_status(status: pallet_survey::Status, include_private: bool) -> Vec<u128> {
+			Survey::surveys_by_status(status, include_private)
+		}
+
+		fn surveys_of_owner(owner: AccountId) -> Vec<u128> {
+			Survey::surveys_of_owner(owner)
+		}
+
+		fn surveys_joined_by(participant: AccountId) -> Vec<u128> {
+			Survey::surveys_joined_by(participant)
+		}
+
+		fn pending_claims(participant: AccountId) -> Vec<(u128, Balance)> {
+			Survey::pending_claims(participant)
+		}
+
+		fn list_rewarded(survey_id: u128, start: Option<AccountId>, limit: u32) -> Vec<AccountId> {
+			Survey::list_rewarded(survey_id, start, limit)
+		}
+
+		fn reward_currency_decimals() -> u8 {
+			Survey::reward_currency_decimals()
+		}
+
+		fn format_balance(amount: Balance) -> (Balance, Balance) {
+			Survey::format_balance(amount)
+		}
+
+		fn reward_all_preview(survey_id: u128) -> (u32, Balance) {
+			Survey::reward_all_preview(survey_id)
+		}
+
+		fn blocks_until_deadline(survey_id: u128) -> Option<BlockNumber> {
+			Survey::blocks_until_deadline(survey_id)
+		}
+
+		fn escrow_surplus(survey_id: u128) -> Option<Balance> {
+			Survey::escrow_surplus(survey_id)
+		}
+
+		fn reward_breakdown(survey_id: u128) -> Option<pallet_survey::RewardBreakdown<Balance>> {
+			Survey::reward_breakdown(survey_id)
+		}
+
+		fn reward_utilization(survey_id: u128) -> Option<Perbill> {
+			Survey::reward_utilization(survey_id)
+		}
+
+		fn total_surveys() -> u64 {
+			Survey::total_surveys()
+		}
+
+		fn reward_receipt(
+			survey_id: u128,
+			participant: AccountId,
+		) -> Option<pallet_survey::RewardReceipt<Balance, BlockNumber>> {
+			Survey::reward_receipt(survey_id, participant)
+		}
+	}
+
 
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {